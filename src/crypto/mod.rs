@@ -0,0 +1,391 @@
+//! Decryption of the workbook stream protected by a FilePass record, 2.2.10 Encryption (Password to Open)
+
+mod md5;
+mod rc4;
+mod sha1;
+
+use crate::{
+    error::{Error, Result},
+    record::filepass::{CryptoApiInfo, Encryption, Rc4Info, XorInfo},
+};
+use md5::md5;
+use rc4::Rc4;
+use sha1::sha1;
+
+/// Password used by Excel when the workbook is only protected against modification
+pub const DEFAULT_PASSWORD: &str = "VelvetSweatshop";
+
+const BLOCK_SIZE: usize = 1024;
+
+pub enum Decryptor {
+    Xor([u8; 16]),
+    Rc4 { base: [u8; 5] },
+    CryptoApi { base: [u8; 20], key_len: usize },
+}
+
+impl Decryptor {
+    /// Verifies the password against the FilePass record, then the default password.
+    ///
+    /// # Arguments
+    ///
+    /// * `encryption` - Decoded FilePass record
+    /// * `password` - Password supplied by the user, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Decryptor>` - Decryptor of the first password verified, or an error if none is
+    pub fn new(encryption: &Encryption, password: Option<&str>) -> Result<Self> {
+        password
+            .into_iter()
+            .chain([DEFAULT_PASSWORD])
+            .find_map(|password| Self::verify(encryption, password))
+            .ok_or(match password {
+                Some(_) => Error::Password,
+                None => Error::FillPass,
+            })
+    }
+
+    fn verify(encryption: &Encryption, password: &str) -> Option<Self> {
+        match encryption {
+            Encryption::Xor(info) => xor(info, password),
+            Encryption::Rc4(info) => rc4(info, password),
+            Encryption::CryptoApi(info) => crypto_api(info, password),
+        }
+    }
+
+    /// Decrypts the whole workbook stream in place, record by record.
+    ///
+    /// Record headers, the records listed in 2.2.10 and the `lbPlyPos` field of BoundSheet8
+    /// are left as is, though they still take their place in the key stream.
+    pub fn decrypt(&self, stream: &mut [u8]) {
+        let mut pos = 0;
+        while pos + 4 <= stream.len() {
+            let r#type = u16::from_le_bytes([stream[pos], stream[pos + 1]]);
+            let len = u16::from_le_bytes([stream[pos + 2], stream[pos + 3]]) as usize;
+            let start = pos + 4;
+            let end = (start + len).min(stream.len());
+            pos = start + len;
+
+            let skip = match r#type {
+                // BOF, FilePass, UsrExcl, FileLock, InterfaceHdr, RRDInfo, RRDHead
+                0x0809 | 0x002F | 0x0194 | 0x0195 | 0x00E1 | 0x0196 | 0x0138 => continue,
+                // BoundSheet8
+                0x0085 => 4,
+                _ => 0,
+            };
+            if start + skip < end {
+                self.decrypt_record(stream, len, start + skip, end);
+            }
+        }
+    }
+
+    fn decrypt_record(&self, stream: &mut [u8], len: usize, from: usize, to: usize) {
+        match self {
+            Decryptor::Xor(xor_array) => {
+                // the XOR array index starts at the end of the record data
+                for (offset, b) in (from..to).zip(&mut stream[from..to]) {
+                    *b = (*b ^ xor_array[(offset + len) % 16]).rotate_right(5);
+                }
+            }
+            _ => {
+                // RC4 is rekeyed for every block of 1024 bytes of the stream
+                let mut offset = from;
+                while offset < to {
+                    let block = offset / BLOCK_SIZE;
+                    let block_end = ((block + 1) * BLOCK_SIZE).min(to);
+                    let mut cipher = Rc4::new(&self.key(block as u32));
+                    cipher.skip(offset % BLOCK_SIZE);
+                    cipher.apply(&mut stream[offset..block_end]);
+                    offset = block_end;
+                }
+            }
+        }
+    }
+
+    fn key(&self, block: u32) -> Vec<u8> {
+        match self {
+            Decryptor::Xor(_) => unreachable!(),
+            Decryptor::Rc4 { base } => md5(&[&base[..], &block.to_le_bytes()].concat()).to_vec(),
+            Decryptor::CryptoApi { base, key_len } => {
+                let hash = sha1(&[&base[..], &block.to_le_bytes()].concat());
+                let mut key = hash[..*key_len].to_vec();
+                // 40-bit keys are padded to 128 bits
+                key.resize(key.len().max(16), 0);
+                key
+            }
+        }
+    }
+}
+
+/// Message padding of MD5 and SHA-1, with the bit length in little or big endian.
+fn pad(data: &[u8], big_endian: bool) -> Vec<u8> {
+    let bits = (data.len() as u64).wrapping_mul(8);
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    if big_endian {
+        message.extend(bits.to_be_bytes());
+    } else {
+        message.extend(bits.to_le_bytes());
+    }
+    message
+}
+
+fn utf16(password: &str) -> Vec<u8> {
+    password.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+// [MS-OFFCRYPTO] 2.3.7.1 - 2.3.7.4
+fn xor(info: &XorInfo, password: &str) -> Option<Decryptor> {
+    // one byte per character, the low byte unless it is zero
+    let password: Vec<u8> = password
+        .encode_utf16()
+        .take(15)
+        .map(|c| match c.to_le_bytes() {
+            [0, high] => high,
+            [low, _] => low,
+        })
+        .collect();
+    if password.is_empty() {
+        return None;
+    }
+
+    let mut verifier = 0u16;
+    for &b in password.iter().rev().chain([&(password.len() as u8)]) {
+        verifier = (((verifier >> 14) & 0x01) | ((verifier << 1) & 0x7FFF)) ^ b as u16;
+    }
+    if verifier ^ 0xCE4B != info.verifier {
+        return None;
+    }
+
+    const PAD: [u8; 15] = [
+        0xBB, 0xFF, 0xFF, 0xBA, 0xFF, 0xFF, 0xB9, 0x80, 0x00, 0xBE, 0x0F, 0x00, 0xBF, 0x0F, 0x00,
+    ];
+    let [low, high] = info.key.to_le_bytes();
+    let mut xor_array = [0u8; 16];
+    for (i, b) in xor_array.iter_mut().enumerate() {
+        let c = password
+            .get(i)
+            .copied()
+            .unwrap_or_else(|| PAD[i - password.len()]);
+        let k = if i % 2 == 0 { low } else { high };
+        *b = (c ^ k).rotate_right(1);
+    }
+    Some(Decryptor::Xor(xor_array))
+}
+
+// [MS-OFFCRYPTO] 2.3.6.2 - 2.3.6.4
+fn rc4(info: &Rc4Info, password: &str) -> Option<Decryptor> {
+    let h0 = md5(&utf16(password));
+    let buffer = [&h0[..5], &info.salt[..]].concat().repeat(16);
+    let h1 = md5(&buffer);
+
+    let mut base = [0u8; 5];
+    base.copy_from_slice(&h1[..5]);
+    let decryptor = Decryptor::Rc4 { base };
+
+    let mut verifier = [&info.verifier[..], &info.verifier_hash[..]].concat();
+    Rc4::new(&decryptor.key(0)).apply(&mut verifier);
+    (md5(&verifier[..16])[..] == verifier[16..]).then_some(decryptor)
+}
+
+// [MS-OFFCRYPTO] 2.3.5.2 - 2.3.5.6
+fn crypto_api(info: &CryptoApiInfo, password: &str) -> Option<Decryptor> {
+    // RC4 with SHA-1
+    if !matches!(info.alg_id, 0x0000 | 0x6801) || !matches!(info.alg_id_hash, 0x0000 | 0x8004) {
+        return None;
+    }
+    let key_size = if info.key_size == 0 {
+        40
+    } else {
+        info.key_size
+    };
+    let key_len = (key_size as usize / 8).clamp(5, 20);
+
+    let base = sha1(&[&info.salt[..], &utf16(password)].concat());
+    let decryptor = Decryptor::CryptoApi { base, key_len };
+
+    let mut verifier = [&info.verifier[..], &info.verifier_hash[..]].concat();
+    Rc4::new(&decryptor.key(0)).apply(&mut verifier);
+    (sha1(&verifier[..16])[..] == verifier[16..]).then_some(decryptor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::filepass;
+    use binrw::BinRead;
+    use std::io::Cursor;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // FilePass record of the encryption type and info
+    fn encryption(enc_type: u16, enc_info: &[u8]) -> Encryption {
+        let mut bytes = (2 + enc_info.len() as u16).to_le_bytes().to_vec();
+        bytes.extend(enc_type.to_le_bytes());
+        bytes.extend(enc_info);
+        let data = filepass::Data::read_le(&mut Cursor::new(bytes)).unwrap();
+        data.decode().unwrap()
+    }
+
+    fn check(encryption: &Encryption) {
+        assert!(Decryptor::new(encryption, Some("password")).is_ok());
+        assert!(matches!(
+            Decryptor::new(encryption, Some("Password")),
+            Err(Error::Password)
+        ));
+        assert!(matches!(
+            Decryptor::new(encryption, None),
+            Err(Error::FillPass)
+        ));
+    }
+
+    #[test]
+    fn xor_verifier() {
+        // 2.3.7.1 password verifier of "password", as in the sheet protection hash
+        check(&encryption(0x0000, &[0x34, 0x12, 0xAF, 0x83]));
+    }
+
+    #[test]
+    fn rc4_verifier() {
+        // 2.3.6.2 key of "password", salt 00..0F and verifier A0..AF
+        let mut enc_info = vec![1, 0, 1, 0];
+        enc_info.extend(0x00..0x10);
+        enc_info.extend(hex("b4d7714c5d5ce0900d82e34abf3aa84f"));
+        enc_info.extend(hex("6dadbce900dec2bdb971739e9e42493e"));
+        check(&encryption(0x0001, &enc_info));
+    }
+
+    #[test]
+    fn crypto_api_verifier() {
+        // 2.3.5.2 128-bit RC4 key of "password", salt 00..0F and verifier A0..AF
+        let mut enc_info = vec![4, 0, 2, 0];
+        // flags, header size, then EncryptionHeader without CSP name
+        for field in [0x24, 32, 0x24, 0, 0x6801, 0x8004, 128, 1, 0, 0, 16] {
+            enc_info.extend(u32::to_le_bytes(field));
+        }
+        enc_info.extend(0x00..0x10);
+        enc_info.extend(hex("715c7634970aa53778b98cf3ebda208d"));
+        enc_info.extend(20u32.to_le_bytes());
+        enc_info.extend(hex("21c30a858b2397d08b2cd59602d45d26bc7c074b"));
+        check(&encryption(0x0001, &enc_info));
+    }
+
+    fn record(r#type: u16, body: &[u8]) -> Vec<u8> {
+        let mut bytes = r#type.to_le_bytes().to_vec();
+        bytes.extend((body.len() as u16).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    // workbook stream with the records left in the clear, a BoundSheet8 and a record across the
+    // first 1024-byte block boundary
+    fn stream() -> Vec<u8> {
+        let mut boundsheet = 0x0000_0400u32.to_le_bytes().to_vec();
+        boundsheet.extend([0, 0, 6, 0]);
+        boundsheet.extend(b"Sheet1");
+        let sst = (0..1500).map(|i| (i * 7) as u8).collect::<Vec<_>>();
+        [
+            record(0x0809, &[0x00, 0x06, 0x05, 0x00].repeat(4)),
+            record(0x002F, &[0x01, 0x00].repeat(27)),
+            record(0x00E1, &[0xB0, 0x04]),
+            record(0x0085, &boundsheet),
+            record(0x00FC, &sst),
+            record(0x0203, &[0x2A; 14]),
+            record(0x000A, &[]),
+        ]
+        .concat()
+    }
+
+    // encrypts the data of the records, byte by byte from its position in the stream and the
+    // length of its record, except the record headers and the fields left in the clear
+    fn encrypt(plain: &[u8], byte: impl Fn(usize, usize, u8) -> u8) -> Vec<u8> {
+        let mut stream = plain.to_vec();
+        let mut pos = 0;
+        while pos < stream.len() {
+            let r#type = u16::from_le_bytes([stream[pos], stream[pos + 1]]);
+            let len = u16::from_le_bytes([stream[pos + 2], stream[pos + 3]]) as usize;
+            let clear = match r#type {
+                0x0809 | 0x002F | 0x00E1 => len,
+                0x0085 => 4,
+                _ => 0,
+            };
+            let from = pos + 4 + clear;
+            for (offset, b) in (from..).zip(&mut stream[from..pos + 4 + len]) {
+                *b = byte(offset, len, *b);
+            }
+            pos += 4 + len;
+        }
+        stream
+    }
+
+    fn round_trip(decryptor: &Decryptor, encrypted: &[u8]) {
+        let plain = stream();
+        // headers, BOF, FilePass, InterfaceHdr and lbPlyPos of BoundSheet8 are in the clear
+        assert_eq!(encrypted[..92], plain[..92]);
+        assert_ne!(encrypted[92..102], plain[92..102]);
+
+        let mut decrypted = encrypted.to_vec();
+        decryptor.decrypt(&mut decrypted);
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn xor_decrypt() {
+        let xor_array: [u8; 16] = std::array::from_fn(|i| i as u8 * 0x11);
+        let decryptor = Decryptor::Xor(xor_array);
+
+        // 2.3.7.4 the XOR array index of the first byte of data is its position plus the length
+        // of the record, 7 for a record of 3 bytes at the start of the stream
+        let mut bytes = record(0x0203, &[0x57, 0xC8, 0xF9]);
+        decryptor.decrypt(&mut bytes);
+        assert_eq!(bytes[4..], [0x01, 0x02, 0x03]);
+
+        let encrypted = encrypt(&stream(), |offset, len, b| {
+            b.rotate_left(5) ^ xor_array[(offset + len) % 16]
+        });
+        round_trip(&decryptor, &encrypted);
+    }
+
+    // keystream of the RC4 key of each 1024-byte block of the stream
+    fn rc4_encrypt(decryptor: &Decryptor) -> Vec<u8> {
+        let plain = stream();
+        let keystreams = (0..plain.len().div_ceil(BLOCK_SIZE))
+            .map(|block| {
+                let mut keystream = vec![0; BLOCK_SIZE];
+                Rc4::new(&decryptor.key(block as u32)).apply(&mut keystream);
+                keystream
+            })
+            .collect::<Vec<_>>();
+        encrypt(&plain, |offset, _, b| {
+            b ^ keystreams[offset / BLOCK_SIZE][offset % BLOCK_SIZE]
+        })
+    }
+
+    #[test]
+    fn rc4_decrypt() {
+        let decryptor = Decryptor::Rc4 {
+            base: [0x01, 0x23, 0x45, 0x67, 0x89],
+        };
+        round_trip(&decryptor, &rc4_encrypt(&decryptor));
+    }
+
+    #[test]
+    fn crypto_api_decrypt() {
+        // 40-bit keys are padded to 128 bits
+        for key_len in [5, 16] {
+            let decryptor = Decryptor::CryptoApi {
+                base: std::array::from_fn(|i| i as u8),
+                key_len,
+            };
+            round_trip(&decryptor, &rc4_encrypt(&decryptor));
+        }
+    }
+}
@@ -7,8 +7,15 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     Binrw(#[from] binrw::Error),
+    #[cfg(feature = "xlsx")]
     #[error(transparent)]
     Xlsx(#[from] umya_spreadsheet::XlsxError),
+    #[cfg(feature = "xlsx")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[cfg(feature = "xlsx")]
+    #[error(transparent)]
+    Xml(#[from] quick_xml::Error),
 
     #[error("File type may not supported")]
     XlsExt,
@@ -17,10 +24,18 @@ pub enum Error {
         expect: crate::record::bof::StreamType,
         actual: crate::record::bof::StreamType,
     },
+    #[error("Sheet not found: {0}")]
+    Sheet(String),
     #[error("Couldn't detect encoding from codepage {0}")]
     CodePage(u16),
-    #[error("Encrypted file not supported")]
+    #[error("Encrypted file needs a password")]
     FillPass,
+    #[error("Wrong password")]
+    Password,
+    #[error("Unsupported encryption {0}.{1}")]
+    Encryption(u16, u16),
+    #[error("Unsupported formula token 0x{0:02X}")]
+    Ptg(u8),
 }
 
 impl Error {
@@ -0,0 +1,841 @@
+use crate::{
+    error::{Error, Result},
+    model::Global,
+    record::{self, supbook::Book},
+    workbook::ErrorKind,
+};
+use binrw::BinRead;
+use encoding_rs::UTF_16LE;
+use std::io::Cursor;
+
+/// Decompiles a BIFF8 parsed formula (2.5.198 Rgce + RgbExtra) into A1-style formula text
+/// without the leading `=`.
+///
+/// `row` and `col` are the zero-based cell the formula belongs to, used to resolve
+/// the relative references of `PtgRefN` and `PtgAreaN`.
+pub fn decompile(global: &Global, rgce: &[u8], rgcb: &[u8], row: u16, col: u16) -> Result<String> {
+    let mut ptgs = Cursor::new(rgce);
+    let mut extra = Cursor::new(rgcb);
+    let mut stack: Vec<String> = Vec::new();
+
+    while (ptgs.position() as usize) < rgce.len() {
+        let ptg = u8::read_le(&mut ptgs)?;
+        // reference class bits (0x60) don't matter for the text
+        let base = if ptg < 0x20 { ptg } else { ptg & 0x1F | 0x20 };
+
+        match base {
+            // PtgExp, PtgTbl
+            0x01 | 0x02 => {
+                let row = u16::read_le(&mut ptgs)?;
+                let col = u16::read_le(&mut ptgs)?;
+                stack.push(cell(row, col, false, false));
+            }
+            // binary operators
+            0x03..=0x11 => {
+                let op = match base {
+                    0x03 => "+",
+                    0x04 => "-",
+                    0x05 => "*",
+                    0x06 => "/",
+                    0x07 => "^",
+                    0x08 => "&",
+                    0x09 => "<",
+                    0x0A => "<=",
+                    0x0B => "=",
+                    0x0C => ">=",
+                    0x0D => ">",
+                    0x0E => "<>",
+                    0x0F => " ",
+                    0x10 => ",",
+                    _ => ":",
+                };
+                let rhs = pop(&mut stack)?;
+                let lhs = pop(&mut stack)?;
+                stack.push(format!("{lhs}{op}{rhs}"));
+            }
+            0x12 => {
+                let operand = pop(&mut stack)?;
+                stack.push(format!("+{operand}"));
+            }
+            0x13 => {
+                let operand = pop(&mut stack)?;
+                stack.push(format!("-{operand}"));
+            }
+            0x14 => {
+                let operand = pop(&mut stack)?;
+                stack.push(format!("{operand}%"));
+            }
+            0x15 => {
+                let operand = pop(&mut stack)?;
+                stack.push(format!("({operand})"));
+            }
+            // PtgMissArg
+            0x16 => stack.push(String::new()),
+            // PtgStr
+            0x17 => {
+                let s = record::ShortXLUnicodeString::read_le(&mut ptgs)?;
+                stack.push(quote(&record::xlstring(UTF_16LE, s.hbyte, &s.bytes)));
+            }
+            // PtgAttr
+            0x19 => {
+                let attr = u8::read_le(&mut ptgs)?;
+                match attr {
+                    // PtgAttrChoose
+                    0x04 => {
+                        let count = u16::read_le(&mut ptgs)?;
+                        ptgs.set_position(ptgs.position() + 2 * (count as u64 + 1));
+                    }
+                    // PtgAttrSum
+                    0x10 => {
+                        ptgs.set_position(ptgs.position() + 2);
+                        let operand = pop(&mut stack)?;
+                        stack.push(format!("SUM({operand})"));
+                    }
+                    // semi, if, goto, baxcel, space(s)
+                    _ => ptgs.set_position(ptgs.position() + 2),
+                }
+            }
+            // PtgErr
+            0x1C => {
+                let code = u8::read_le(&mut ptgs)?;
+                stack.push(ErrorKind::from_code(code).to_string());
+            }
+            // PtgBool
+            0x1D => {
+                let b = u8::read_le(&mut ptgs)?;
+                stack.push(if b == 0x01 { "TRUE" } else { "FALSE" }.to_string());
+            }
+            // PtgInt
+            0x1E => stack.push(u16::read_le(&mut ptgs)?.to_string()),
+            // PtgNum
+            0x1F => stack.push(f64::read_le(&mut ptgs)?.to_string()),
+            // PtgArray
+            0x20 => {
+                ptgs.set_position(ptgs.position() + 7);
+                stack.push(array(&mut extra)?);
+            }
+            // PtgFunc
+            0x21 => {
+                let iftab = u16::read_le(&mut ptgs)?;
+                let (name, argc, _) = function(iftab).ok_or(Error::Ptg(ptg))?;
+                let args = pop_args(&mut stack, argc as usize)?;
+                stack.push(format!("{name}({})", args.join(",")));
+            }
+            // PtgFuncVar
+            0x22 => {
+                let argc = u8::read_le(&mut ptgs)? & 0x7F;
+                let iftab = u16::read_le(&mut ptgs)? & 0x7FFF;
+                let mut args = pop_args(&mut stack, argc as usize)?;
+                let name = if iftab == 0x00FF {
+                    // user defined function, the name is the first argument
+                    if args.is_empty() {
+                        return Err(Error::Ptg(ptg));
+                    }
+                    args.remove(0)
+                } else {
+                    function(iftab).ok_or(Error::Ptg(ptg))?.0.to_string()
+                };
+                stack.push(format!("{name}({})", args.join(",")));
+            }
+            // PtgName
+            0x23 => {
+                let iname = u32::read_le(&mut ptgs)?;
+                stack.push(name(global, iname));
+            }
+            // PtgRef, PtgRefN
+            0x24 | 0x2C => {
+                let r = u16::read_le(&mut ptgs)?;
+                let c = u16::read_le(&mut ptgs)?;
+                stack.push(reference(r, c, (base == 0x2C).then_some((row, col))));
+            }
+            // PtgArea, PtgAreaN
+            0x25 | 0x2D => {
+                let area = Area::read_le(&mut ptgs)?;
+                stack.push(area.text((base == 0x2D).then_some((row, col))));
+            }
+            // PtgMemArea
+            0x26 => {
+                ptgs.set_position(ptgs.position() + 6);
+                // PtgExtraMem
+                let count = u16::read_le(&mut extra)?;
+                extra.set_position(extra.position() + 8 * count as u64);
+            }
+            // PtgMemErr, PtgMemNoMem
+            0x27 | 0x28 => ptgs.set_position(ptgs.position() + 6),
+            // PtgMemFunc
+            0x29 => ptgs.set_position(ptgs.position() + 2),
+            // PtgRefErr
+            0x2A => {
+                ptgs.set_position(ptgs.position() + 4);
+                stack.push("#REF!".to_string());
+            }
+            // PtgAreaErr
+            0x2B => {
+                ptgs.set_position(ptgs.position() + 8);
+                stack.push("#REF!".to_string());
+            }
+            // PtgNameX
+            0x39 => {
+                let ixti = u16::read_le(&mut ptgs)?;
+                let iname = u32::read_le(&mut ptgs)?;
+                stack.push(name_x(global, ixti, iname));
+            }
+            // PtgRef3d
+            0x3A => {
+                let ixti = u16::read_le(&mut ptgs)?;
+                let r = u16::read_le(&mut ptgs)?;
+                let c = u16::read_le(&mut ptgs)?;
+                let sheets = sheets(global, ixti);
+                stack.push(format!("{sheets}{}", reference(r, c, None)));
+            }
+            // PtgArea3d
+            0x3B => {
+                let ixti = u16::read_le(&mut ptgs)?;
+                let area = Area::read_le(&mut ptgs)?;
+                let sheets = sheets(global, ixti);
+                stack.push(format!("{sheets}{}", area.text(None)));
+            }
+            // PtgRefErr3d
+            0x3C => {
+                let ixti = u16::read_le(&mut ptgs)?;
+                ptgs.set_position(ptgs.position() + 4);
+                stack.push(format!("{}#REF!", sheets(global, ixti)));
+            }
+            // PtgAreaErr3d
+            0x3D => {
+                let ixti = u16::read_le(&mut ptgs)?;
+                ptgs.set_position(ptgs.position() + 8);
+                stack.push(format!("{}#REF!", sheets(global, ixti)));
+            }
+            _ => return Err(Error::Ptg(ptg)),
+        }
+    }
+
+    pop(&mut stack)
+}
+
+fn pop(stack: &mut Vec<String>) -> Result<String> {
+    stack.pop().ok_or(Error::msg("formula stack underflow"))
+}
+
+fn pop_args(stack: &mut Vec<String>, argc: usize) -> Result<Vec<String>> {
+    if stack.len() < argc {
+        return Err(Error::msg("formula stack underflow"));
+    }
+    Ok(stack.split_off(stack.len() - argc))
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+/// Zero-based column index to its letters, e.g. 0 to `A`, 27 to `AB`.
+pub fn column(col: u16) -> String {
+    let mut col = col as u32 + 1;
+    let mut name = Vec::new();
+    while col > 0 {
+        let rem = (col - 1) % 26;
+        name.push(b'A' + rem as u8);
+        col = (col - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Zero-based row and column to an A1-style reference, e.g. (0, 0) to `A1`.
+pub fn cell(row: u16, col: u16, row_abs: bool, col_abs: bool) -> String {
+    format!(
+        "{}{}{}{}",
+        if col_abs { "$" } else { "" },
+        column(col),
+        if row_abs { "$" } else { "" },
+        row as u32 + 1
+    )
+}
+
+// 2.5.198.105 RgceLoc & 2.5.198.88 RgceLocRel
+fn reference(row: u16, col: u16, base: Option<(u16, u16)>) -> String {
+    let (row, col, row_rel, col_rel) = locate(row, col, base);
+    cell(row, col, !row_rel, !col_rel)
+}
+
+fn locate(row: u16, col: u16, base: Option<(u16, u16)>) -> (u16, u16, bool, bool) {
+    let row_rel = col & 0x8000 == 0x8000;
+    let col_rel = col & 0x4000 == 0x4000;
+    let mut c = col & 0x3FFF;
+    let mut r = row;
+    if let Some((brow, bcol)) = base {
+        if row_rel {
+            r = brow.wrapping_add(row);
+        }
+        if col_rel {
+            // the column offset is a signed byte in BIFF8
+            c = (bcol as u8).wrapping_add(c as u8) as u16;
+        }
+    }
+    (r, c, row_rel, col_rel)
+}
+
+// 2.5.198.104 RgceArea
+#[derive(Debug, BinRead)]
+#[br(little)]
+struct Area {
+    row_first: u16,
+    row_last: u16,
+    col_first: u16,
+    col_last: u16,
+}
+
+impl Area {
+    fn text(&self, base: Option<(u16, u16)>) -> String {
+        let (r1, c1, r1_rel, c1_rel) = locate(self.row_first, self.col_first, base);
+        let (r2, c2, r2_rel, c2_rel) = locate(self.row_last, self.col_last, base);
+        if r1 == 0 && r2 == 0xFFFF {
+            // whole columns
+            let c1 = format!("{}{}", if c1_rel { "" } else { "$" }, column(c1));
+            let c2 = format!("{}{}", if c2_rel { "" } else { "$" }, column(c2));
+            return format!("{c1}:{c2}");
+        }
+        if c1 == 0 && c2 == 0xFF {
+            // whole rows
+            let r1 = format!("{}{}", if r1_rel { "" } else { "$" }, r1 as u32 + 1);
+            let r2 = format!("{}{}", if r2_rel { "" } else { "$" }, r2 as u32 + 1);
+            return format!("{r1}:{r2}");
+        }
+        format!(
+            "{}:{}",
+            cell(r1, c1, !r1_rel, !c1_rel),
+            cell(r2, c2, !r2_rel, !c2_rel)
+        )
+    }
+}
+
+// 2.5.198.8 PtgExtraArray
+fn array(extra: &mut Cursor<&[u8]>) -> Result<String> {
+    let cols = u8::read_le(extra)? as u16 + 1;
+    let rows = u16::read_le(extra)? + 1;
+    let mut lines = Vec::with_capacity(rows as usize);
+    for _ in 0..rows {
+        let mut values = Vec::with_capacity(cols as usize);
+        for _ in 0..cols {
+            // 2.5.192 SerAr
+            let value = match u8::read_le(extra)? {
+                0x01 => f64::read_le(extra)?.to_string(),
+                0x02 => {
+                    let s = record::XLUnicodeString::read_le(extra)?;
+                    quote(&record::xlstring(UTF_16LE, s.hbyte, &s.bytes))
+                }
+                0x04 => {
+                    let b = u8::read_le(extra)?;
+                    extra.set_position(extra.position() + 7);
+                    if b == 0x01 { "TRUE" } else { "FALSE" }.to_string()
+                }
+                0x10 => {
+                    let code = u8::read_le(extra)?;
+                    extra.set_position(extra.position() + 7);
+                    ErrorKind::from_code(code).to_string()
+                }
+                _ => {
+                    extra.set_position(extra.position() + 8);
+                    String::new()
+                }
+            };
+            values.push(value);
+        }
+        lines.push(values.join(","));
+    }
+    Ok(format!("{{{}}}", lines.join(";")))
+}
+
+fn name(global: &Global, iname: u32) -> String {
+    global
+        .names
+        .get((iname as usize).wrapping_sub(1))
+        .map(|lbl| lbl.name.clone())
+        .unwrap_or("#NAME?".to_string())
+}
+
+fn name_x(global: &Global, ixti: u16, iname: u32) -> String {
+    let Some(xti) = global.xtis.get(ixti as usize) else {
+        return "#NAME?".to_string();
+    };
+    let Some(supbook) = global.supbooks.get(xti.isupbook as usize) else {
+        return "#NAME?".to_string();
+    };
+    match &supbook.book {
+        Book::Internal => name(global, iname),
+        Book::AddIn => supbook
+            .names
+            .get((iname as usize).wrapping_sub(1))
+            .cloned()
+            .unwrap_or("#NAME?".to_string()),
+        Book::External { path, .. } => {
+            let name = supbook
+                .names
+                .get((iname as usize).wrapping_sub(1))
+                .map(String::as_str)
+                .unwrap_or("#NAME?");
+            format!("'[{path}]'!{name}")
+        }
+    }
+}
+
+/// Sheet prefix of a 3D reference, e.g. `Sheet1!` or `'Sheet 1:Sheet 3'!`.
+fn sheets(global: &Global, ixti: u16) -> String {
+    let Some(xti) = global.xtis.get(ixti as usize) else {
+        return "#REF!".to_string();
+    };
+    let Some(supbook) = global.supbooks.get(xti.isupbook as usize) else {
+        return "#REF!".to_string();
+    };
+    let (book, names) = match &supbook.book {
+        Book::Internal => (None, &global.sheet_names),
+        Book::External { path, sheets, .. } => (Some(path), sheets),
+        Book::AddIn => return String::new(),
+    };
+    if xti.itab_first == -2 {
+        // workbook-level reference
+        return book.map(|path| format!("'[{path}]'!")).unwrap_or_default();
+    }
+    let sheet = |itab: i16| names.get(itab as usize).cloned();
+    let (Some(first), Some(last)) = (sheet(xti.itab_first), sheet(xti.itab_last)) else {
+        return "#REF!".to_string();
+    };
+    let mut prefix = match book {
+        Some(path) => format!("[{path}]{first}"),
+        None => first,
+    };
+    if xti.itab_last != xti.itab_first {
+        prefix = format!("{prefix}:{last}");
+    }
+    sheet_prefix(&prefix)
+}
+
+/// Quotes a sheet name when needed, e.g. `Sheet1!` or `'My Sheet'!`.
+pub fn sheet_prefix(name: &str) -> String {
+    let plain = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == ':')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if plain {
+        format!("{name}!")
+    } else {
+        format!("'{}'!", name.replace('\'', "''"))
+    }
+}
+
+// 2.5.198.17 Ftab, (name, min args, max args)
+fn function(iftab: u16) -> Option<(&'static str, u8, u8)> {
+    let func = match iftab {
+        0 => ("COUNT", 0, 30),
+        1 => ("IF", 2, 3),
+        2 => ("ISNA", 1, 1),
+        3 => ("ISERROR", 1, 1),
+        4 => ("SUM", 0, 30),
+        5 => ("AVERAGE", 1, 30),
+        6 => ("MIN", 1, 30),
+        7 => ("MAX", 1, 30),
+        8 => ("ROW", 0, 1),
+        9 => ("COLUMN", 0, 1),
+        10 => ("NA", 0, 0),
+        11 => ("NPV", 2, 30),
+        12 => ("STDEV", 1, 30),
+        13 => ("DOLLAR", 1, 2),
+        14 => ("FIXED", 1, 3),
+        15 => ("SIN", 1, 1),
+        16 => ("COS", 1, 1),
+        17 => ("TAN", 1, 1),
+        18 => ("ATAN", 1, 1),
+        19 => ("PI", 0, 0),
+        20 => ("SQRT", 1, 1),
+        21 => ("EXP", 1, 1),
+        22 => ("LN", 1, 1),
+        23 => ("LOG10", 1, 1),
+        24 => ("ABS", 1, 1),
+        25 => ("INT", 1, 1),
+        26 => ("SIGN", 1, 1),
+        27 => ("ROUND", 2, 2),
+        28 => ("LOOKUP", 2, 3),
+        29 => ("INDEX", 2, 4),
+        30 => ("REPT", 2, 2),
+        31 => ("MID", 3, 3),
+        32 => ("LEN", 1, 1),
+        33 => ("VALUE", 1, 1),
+        34 => ("TRUE", 0, 0),
+        35 => ("FALSE", 0, 0),
+        36 => ("AND", 1, 30),
+        37 => ("OR", 1, 30),
+        38 => ("NOT", 1, 1),
+        39 => ("MOD", 2, 2),
+        40 => ("DCOUNT", 3, 3),
+        41 => ("DSUM", 3, 3),
+        42 => ("DAVERAGE", 3, 3),
+        43 => ("DMIN", 3, 3),
+        44 => ("DMAX", 3, 3),
+        45 => ("DSTDEV", 3, 3),
+        46 => ("VAR", 1, 30),
+        47 => ("DVAR", 3, 3),
+        48 => ("TEXT", 2, 2),
+        49 => ("LINEST", 1, 4),
+        50 => ("TREND", 1, 4),
+        51 => ("LOGEST", 1, 4),
+        52 => ("GROWTH", 1, 4),
+        56 => ("PV", 3, 5),
+        57 => ("FV", 3, 5),
+        58 => ("NPER", 3, 5),
+        59 => ("PMT", 3, 5),
+        60 => ("RATE", 3, 6),
+        61 => ("MIRR", 3, 3),
+        62 => ("IRR", 1, 2),
+        63 => ("RAND", 0, 0),
+        64 => ("MATCH", 2, 3),
+        65 => ("DATE", 3, 3),
+        66 => ("TIME", 3, 3),
+        67 => ("DAY", 1, 1),
+        68 => ("MONTH", 1, 1),
+        69 => ("YEAR", 1, 1),
+        70 => ("WEEKDAY", 1, 2),
+        71 => ("HOUR", 1, 1),
+        72 => ("MINUTE", 1, 1),
+        73 => ("SECOND", 1, 1),
+        74 => ("NOW", 0, 0),
+        75 => ("AREAS", 1, 1),
+        76 => ("ROWS", 1, 1),
+        77 => ("COLUMNS", 1, 1),
+        78 => ("OFFSET", 3, 5),
+        82 => ("SEARCH", 2, 3),
+        83 => ("TRANSPOSE", 1, 1),
+        86 => ("TYPE", 1, 1),
+        97 => ("ATAN2", 2, 2),
+        98 => ("ASIN", 1, 1),
+        99 => ("ACOS", 1, 1),
+        100 => ("CHOOSE", 2, 30),
+        101 => ("HLOOKUP", 3, 4),
+        102 => ("VLOOKUP", 3, 4),
+        105 => ("ISREF", 1, 1),
+        109 => ("LOG", 1, 2),
+        111 => ("CHAR", 1, 1),
+        112 => ("LOWER", 1, 1),
+        113 => ("UPPER", 1, 1),
+        114 => ("PROPER", 1, 1),
+        115 => ("LEFT", 1, 2),
+        116 => ("RIGHT", 1, 2),
+        117 => ("EXACT", 2, 2),
+        118 => ("TRIM", 1, 1),
+        119 => ("REPLACE", 4, 4),
+        120 => ("SUBSTITUTE", 3, 4),
+        121 => ("CODE", 1, 1),
+        124 => ("FIND", 2, 3),
+        125 => ("CELL", 1, 2),
+        126 => ("ISERR", 1, 1),
+        127 => ("ISTEXT", 1, 1),
+        128 => ("ISNUMBER", 1, 1),
+        129 => ("ISBLANK", 1, 1),
+        130 => ("T", 1, 1),
+        131 => ("N", 1, 1),
+        140 => ("DATEVALUE", 1, 1),
+        141 => ("TIMEVALUE", 1, 1),
+        142 => ("SLN", 3, 3),
+        143 => ("SYD", 4, 4),
+        144 => ("DDB", 4, 5),
+        148 => ("INDIRECT", 1, 2),
+        162 => ("CLEAN", 1, 1),
+        163 => ("MDETERM", 1, 1),
+        164 => ("MINVERSE", 1, 1),
+        165 => ("MMULT", 2, 2),
+        167 => ("IPMT", 4, 6),
+        168 => ("PPMT", 4, 6),
+        169 => ("COUNTA", 0, 30),
+        183 => ("PRODUCT", 0, 30),
+        184 => ("FACT", 1, 1),
+        189 => ("DPRODUCT", 3, 3),
+        190 => ("ISNONTEXT", 1, 1),
+        193 => ("STDEVP", 1, 30),
+        194 => ("VARP", 1, 30),
+        195 => ("DSTDEVP", 3, 3),
+        196 => ("DVARP", 3, 3),
+        197 => ("TRUNC", 1, 2),
+        198 => ("ISLOGICAL", 1, 1),
+        199 => ("DCOUNTA", 3, 3),
+        204 => ("USDOLLAR", 1, 2),
+        205 => ("FINDB", 2, 3),
+        206 => ("SEARCHB", 2, 3),
+        207 => ("REPLACEB", 4, 4),
+        208 => ("LEFTB", 1, 2),
+        209 => ("RIGHTB", 1, 2),
+        210 => ("MIDB", 3, 3),
+        211 => ("LENB", 1, 1),
+        212 => ("ROUNDUP", 2, 2),
+        213 => ("ROUNDDOWN", 2, 2),
+        214 => ("ASC", 1, 1),
+        215 => ("DBCS", 1, 1),
+        216 => ("RANK", 2, 3),
+        219 => ("ADDRESS", 2, 5),
+        220 => ("DAYS360", 2, 3),
+        221 => ("TODAY", 0, 0),
+        222 => ("VDB", 5, 7),
+        227 => ("MEDIAN", 1, 30),
+        228 => ("SUMPRODUCT", 1, 30),
+        229 => ("SINH", 1, 1),
+        230 => ("COSH", 1, 1),
+        231 => ("TANH", 1, 1),
+        232 => ("ASINH", 1, 1),
+        233 => ("ACOSH", 1, 1),
+        234 => ("ATANH", 1, 1),
+        235 => ("DGET", 3, 3),
+        244 => ("INFO", 1, 1),
+        247 => ("DB", 4, 5),
+        252 => ("FREQUENCY", 2, 2),
+        261 => ("ERROR.TYPE", 1, 1),
+        269 => ("AVEDEV", 1, 30),
+        270 => ("BETADIST", 3, 5),
+        271 => ("GAMMALN", 1, 1),
+        272 => ("BETAINV", 3, 5),
+        273 => ("BINOMDIST", 4, 4),
+        274 => ("CHIDIST", 2, 2),
+        275 => ("CHIINV", 2, 2),
+        276 => ("COMBIN", 2, 2),
+        277 => ("CONFIDENCE", 3, 3),
+        278 => ("CRITBINOM", 3, 3),
+        279 => ("EVEN", 1, 1),
+        280 => ("EXPONDIST", 3, 3),
+        281 => ("FDIST", 3, 3),
+        282 => ("FINV", 3, 3),
+        283 => ("FISHER", 1, 1),
+        284 => ("FISHERINV", 1, 1),
+        285 => ("FLOOR", 2, 2),
+        286 => ("GAMMADIST", 4, 4),
+        287 => ("GAMMAINV", 3, 3),
+        288 => ("CEILING", 2, 2),
+        289 => ("HYPGEOMDIST", 4, 4),
+        290 => ("LOGNORMDIST", 3, 3),
+        291 => ("LOGINV", 3, 3),
+        292 => ("NEGBINOMDIST", 3, 3),
+        293 => ("NORMDIST", 4, 4),
+        294 => ("NORMSDIST", 1, 1),
+        295 => ("NORMINV", 3, 3),
+        296 => ("NORMSINV", 1, 1),
+        297 => ("STANDARDIZE", 3, 3),
+        298 => ("ODD", 1, 1),
+        299 => ("PERMUT", 2, 2),
+        300 => ("POISSON", 3, 3),
+        301 => ("TDIST", 3, 3),
+        302 => ("WEIBULL", 4, 4),
+        303 => ("SUMXMY2", 2, 2),
+        304 => ("SUMX2MY2", 2, 2),
+        305 => ("SUMX2PY2", 2, 2),
+        306 => ("CHITEST", 2, 2),
+        307 => ("CORREL", 2, 2),
+        308 => ("COVAR", 2, 2),
+        309 => ("FORECAST", 3, 3),
+        310 => ("FTEST", 2, 2),
+        311 => ("INTERCEPT", 2, 2),
+        312 => ("PEARSON", 2, 2),
+        313 => ("RSQ", 2, 2),
+        314 => ("STEYX", 2, 2),
+        315 => ("SLOPE", 2, 2),
+        316 => ("TTEST", 4, 4),
+        317 => ("PROB", 3, 4),
+        318 => ("DEVSQ", 1, 30),
+        319 => ("GEOMEAN", 1, 30),
+        320 => ("HARMEAN", 1, 30),
+        321 => ("SUMSQ", 0, 30),
+        322 => ("KURT", 1, 30),
+        323 => ("SKEW", 1, 30),
+        324 => ("ZTEST", 2, 3),
+        325 => ("LARGE", 2, 2),
+        326 => ("SMALL", 2, 2),
+        327 => ("QUARTILE", 2, 2),
+        328 => ("PERCENTILE", 2, 2),
+        329 => ("PERCENTRANK", 2, 3),
+        330 => ("MODE", 1, 30),
+        331 => ("TRIMMEAN", 2, 2),
+        332 => ("TINV", 2, 2),
+        336 => ("CONCATENATE", 0, 30),
+        337 => ("POWER", 2, 2),
+        342 => ("RADIANS", 1, 1),
+        343 => ("DEGREES", 1, 1),
+        344 => ("SUBTOTAL", 2, 30),
+        345 => ("SUMIF", 2, 3),
+        346 => ("COUNTIF", 2, 2),
+        347 => ("COUNTBLANK", 1, 1),
+        350 => ("ISPMT", 4, 4),
+        351 => ("DATEDIF", 3, 3),
+        352 => ("DATESTRING", 1, 1),
+        353 => ("NUMBERSTRING", 2, 2),
+        354 => ("ROMAN", 1, 2),
+        358 => ("GETPIVOTDATA", 2, 30),
+        359 => ("HYPERLINK", 1, 2),
+        360 => ("PHONETIC", 1, 1),
+        361 => ("AVERAGEA", 1, 30),
+        362 => ("MAXA", 1, 30),
+        363 => ("MINA", 1, 30),
+        364 => ("STDEVPA", 1, 30),
+        365 => ("VARPA", 1, 30),
+        366 => ("STDEVA", 1, 30),
+        367 => ("VARA", 1, 30),
+        368 => ("BAHTTEXT", 1, 1),
+        _ => return None,
+    };
+    Some(func)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decompile;
+    use crate::{
+        model::Global,
+        record::{externsheet::Xti, supbook},
+    };
+    use binrw::BinRead;
+    use std::io::Cursor;
+
+    fn global() -> Global {
+        // SupBook of the same workbook
+        let bytes = [4, 0, 2, 0, 0x01, 0x04];
+        Global {
+            sheet_names: vec!["Sheet1".to_string(), "My Sheet".to_string()],
+            supbooks: vec![supbook::Data::read_le(&mut Cursor::new(bytes)).unwrap()],
+            xtis: vec![
+                Xti {
+                    isupbook: 0,
+                    itab_first: 0,
+                    itab_last: 0,
+                },
+                Xti {
+                    isupbook: 0,
+                    itab_first: 0,
+                    itab_last: 1,
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn text(rgce: &[u8]) -> String {
+        decompile(&global(), rgce, &[], 5, 3).unwrap()
+    }
+
+    fn int(num: u16) -> Vec<u8> {
+        [&[0x1E][..], &num.to_le_bytes()].concat()
+    }
+
+    fn ptg(ptg: u8, fields: &[u16]) -> Vec<u8> {
+        [
+            vec![ptg],
+            fields.iter().flat_map(|f| f.to_le_bytes()).collect(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn operators() {
+        let cases: [(Vec<u8>, &str); 5] = [
+            // 1+2*3, the tokens are in postfix order
+            ([int(1), int(2), int(3), vec![0x05, 0x03]].concat(), "1+2*3"),
+            // (1+2)*3 keeps its PtgParen
+            (
+                [int(1), int(2), vec![0x03, 0x15], int(3), vec![0x05]].concat(),
+                "(1+2)*3",
+            ),
+            ([int(5), vec![0x13, 0x14]].concat(), "-5%"),
+            (
+                [int(2), int(3), vec![0x07], int(1), vec![0x0E]].concat(),
+                "2^3<>1",
+            ),
+            ([int(1), int(2), vec![0x08]].concat(), "1&2"),
+        ];
+        for (rgce, expected) in cases {
+            assert_eq!(text(&rgce), expected);
+        }
+    }
+
+    #[test]
+    fn references() {
+        let cases: [(Vec<u8>, &str); 8] = [
+            // PtgRef, relative and absolute rows and columns
+            (ptg(0x24, &[0, 0xC000]), "A1"),
+            (ptg(0x24, &[0, 1]), "$B$1"),
+            (ptg(0x44, &[9, 0x8002]), "$C10"),
+            (ptg(0x64, &[9, 0x4002]), "C$10"),
+            // PtgArea, cells and whole columns
+            (ptg(0x25, &[0, 9, 0xC000, 0xC001]), "A1:B10"),
+            (ptg(0x25, &[0, 0xFFFF, 0xC000, 0x0001]), "A:$B"),
+            // PtgRefN and PtgAreaN, offsets from the cell D6
+            (ptg(0x2C, &[1, 0xC0FF]), "C7"),
+            (ptg(0x2D, &[0, 2, 0xC000, 0x0001]), "D6:$B$3"),
+        ];
+        for (rgce, expected) in cases {
+            assert_eq!(text(&rgce), expected);
+        }
+    }
+
+    #[test]
+    fn references_3d() {
+        // PtgRef3d and PtgArea3d through the Xti of the EXTERNSHEET record
+        assert_eq!(text(&ptg(0x3A, &[0, 0, 0xC000])), "Sheet1!A1");
+        assert_eq!(
+            text(&ptg(0x3B, &[1, 0, 1, 0, 1])),
+            "'Sheet1:My Sheet'!$A$1:$B$2"
+        );
+        assert_eq!(text(&ptg(0x3C, &[0, 0, 0])), "Sheet1!#REF!");
+    }
+
+    #[test]
+    fn functions() {
+        let area = ptg(0x25, &[0, 9, 0xC000, 0xC000]);
+        let a1 = ptg(0x44, &[0, 0xC000]);
+        let cases: [(Vec<u8>, &str); 5] = [
+            // PtgFunc ABS and PtgFuncVar SUM
+            ([int(1), ptg(0x41, &[24])].concat(), "ABS(1)"),
+            (
+                [int(1), int(2), vec![0x42, 2], 4u16.to_le_bytes().to_vec()].concat(),
+                "SUM(1,2)",
+            ),
+            // PtgAttrSum
+            ([area, vec![0x19, 0x10, 0, 0]].concat(), "SUM(A1:A10)"),
+            // PtgAttrIf and PtgAttrGoto around the arguments of IF
+            (
+                [
+                    a1.clone(),
+                    vec![0x19, 0x02, 7, 0],
+                    int(1),
+                    vec![0x19, 0x08, 6, 0],
+                ]
+                .into_iter()
+                .chain([int(2), vec![0x19, 0x08, 3, 0, 0x42, 3, 1, 0]])
+                .collect::<Vec<_>>()
+                .concat(),
+                "IF(A1,1,2)",
+            ),
+            // PtgAttrChoose with its jump table
+            (
+                [int(1), vec![0x19, 0x04, 2, 0, 6, 0, 13, 0, 20, 0], int(10)]
+                    .into_iter()
+                    .chain([vec![0x19, 0x08, 10, 0], int(20), vec![0x19, 0x08, 3, 0]])
+                    .chain([vec![0x42, 3, 100, 0]])
+                    .collect::<Vec<_>>()
+                    .concat(),
+                "CHOOSE(1,10,20)",
+            ),
+        ];
+        for (rgce, expected) in cases {
+            assert_eq!(text(&rgce), expected);
+        }
+    }
+
+    #[test]
+    fn strings() {
+        // compressed, with a quote to escape
+        let mut rgce = vec![0x17, 3, 0x00];
+        rgce.extend(b"a\"b");
+        assert_eq!(text(&rgce), r#""a""b""#);
+
+        // UTF-16, longer than 127 characters
+        let s = "漢字".repeat(100);
+        let mut rgce = vec![0x17, 200, 0x01];
+        rgce.extend(s.encode_utf16().flat_map(u16::to_le_bytes));
+        rgce.extend([0x1E, 1, 0, 0x08]);
+        assert_eq!(text(&rgce), format!("\"{s}\"&1"));
+    }
+}
@@ -1,14 +1,14 @@
 use super::record::{
-    font,
-    xf::{self, HorizAlign, Pattern, VertAlign},
+    array, externsheet::Xti, font, lbl, shrfmla, supbook, table, xf, xfext::ExtProp,
 };
 use std::collections::HashMap;
-use umya_spreadsheet::{HorizontalAlignmentValues, PatternValues, VerticalAlignmentValues};
 
+/// Formula shared by the cells pointing to its anchor with `PtgExp` or `PtgTbl`.
 #[derive(Debug)]
-pub enum Value<'a> {
-    String(&'a str),
-    Number(f64),
+pub enum Shared {
+    Formula(shrfmla::Data),
+    Array(array::Data),
+    Table(table::Data),
 }
 
 #[derive(Debug, Default)]
@@ -18,57 +18,237 @@ pub struct Global {
     pub fonts: Vec<font::Data>,
     pub formats: HashMap<u32, String>,
     pub xfs: Vec<xf::Data>,
+    // full colors and other properties on top of the XFs, by ixfe
+    pub xf_exts: HashMap<u16, Vec<ExtProp>>,
+    // theme part of the Theme record, when not the default theme
+    pub theme: Option<Vec<u8>>,
+    // formula context
+    pub sheet_names: Vec<String>,
+    pub supbooks: Vec<supbook::Data>,
+    pub xtis: Vec<Xti>,
+    pub names: Vec<lbl::Data>,
 }
 
-impl From<HorizAlign> for HorizontalAlignmentValues {
-    fn from(align: HorizAlign) -> HorizontalAlignmentValues {
-        match align {
-            HorizAlign::General => HorizontalAlignmentValues::General,
-            HorizAlign::Left => HorizontalAlignmentValues::Left,
-            HorizAlign::Center => HorizontalAlignmentValues::Center,
-            HorizAlign::Right => HorizontalAlignmentValues::Right,
-            HorizAlign::Fill => HorizontalAlignmentValues::Fill,
-            HorizAlign::Justify => HorizontalAlignmentValues::Justify,
-            HorizAlign::CenterContinuous => HorizontalAlignmentValues::CenterContinuous,
-            HorizAlign::Distributed => HorizontalAlignmentValues::Distributed,
+impl Global {
+    /// FONT record of a font index, as in XF records and formatting runs.
+    #[cfg(feature = "xlsx")]
+    pub fn font(&self, ifnt: u16) -> Option<&font::Data> {
+        // there is no font with index 4
+        let index = match ifnt {
+            0..=3 => ifnt,
+            4 => return None,
+            _ => ifnt - 1,
+        };
+        self.fonts.get(index as usize)
+    }
+
+    /// Whether the number format of the XF is a date or time format.
+    pub fn is_date(&self, ixfe: usize) -> bool {
+        let Some(xf) = self.xfs.get(ixfe) else {
+            return false;
+        };
+        if matches!(xf.ifmt, 1..=4 | 9..=22 | 27..=40 | 45..=62 | 67..=81) {
+            matches!(xf.ifmt, 14..=22 | 27..=36 | 45..=47 | 50..=58 | 71..=81)
+        } else if let Some(format) = self.formats.get(&xf.ifmt.into()) {
+            is_date_format(format)
+        } else {
+            false
         }
     }
+
+    /// Style XF of an XF, the XF itself for a style XF, or the parent style XF of a cell XF.
+    pub fn style_xf(&self, ixfe: u16) -> Option<u16> {
+        let xf = self.xfs.get(ixfe as usize)?;
+        Some(if xf.protection.is_style() {
+            ixfe
+        } else {
+            xf.protection.parent_ixfe()
+        })
+    }
 }
 
-impl From<VertAlign> for VerticalAlignmentValues {
-    fn from(align: VertAlign) -> VerticalAlignmentValues {
-        match align {
-            VertAlign::Top => VerticalAlignmentValues::Top,
-            VertAlign::Center => VerticalAlignmentValues::Center,
-            VertAlign::Bottom => VerticalAlignmentValues::Bottom,
-            VertAlign::Justify => VerticalAlignmentValues::Justify,
-            VertAlign::Distributed => VerticalAlignmentValues::Distributed,
+/// Whether a custom number format has date or time tokens, outside of its literals and colors.
+fn is_date_format(format: &str) -> bool {
+    let format = format.to_lowercase();
+    let mut tokens = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // quoted literal
+            '"' => _ = chars.by_ref().find(|&c| c == '"'),
+            // escaped character, or padding with the width or repeats of a character
+            '\\' | '_' | '*' => _ = chars.next(),
+            // color, condition or locale, except elapsed time e.g. `[h]`, `[mm]`
+            '[' => {
+                let section = chars.by_ref().take_while(|&c| c != ']').collect::<String>();
+                let mut letters = section.chars();
+                if letters
+                    .next()
+                    .is_some_and(|c| matches!(c, 'h' | 'm' | 's') && letters.all(|l| l == c))
+                {
+                    return true;
+                }
+            }
+            _ => tokens.push(c),
         }
     }
+    tokens.contains("a/p") || tokens.contains(['y', 'm', 'd', 'h', 's'])
 }
 
-impl From<Pattern> for PatternValues {
-    fn from(pattern: Pattern) -> PatternValues {
-        match pattern {
-            Pattern::None => PatternValues::None,
-            Pattern::Solid => PatternValues::Solid,
-            Pattern::MediumGray => PatternValues::MediumGray,
-            Pattern::DarkGray => PatternValues::DarkGray,
-            Pattern::LightGray => PatternValues::LightGray,
-            Pattern::DarkHorizontal => PatternValues::DarkHorizontal,
-            Pattern::DarkVertical => PatternValues::DarkVertical,
-            Pattern::DarkDown => PatternValues::DarkDown,
-            Pattern::DarkUp => PatternValues::DarkUp,
-            Pattern::DarkGrid => PatternValues::DarkGrid,
-            Pattern::DarkTrellis => PatternValues::DarkTrellis,
-            Pattern::LightHorizontal => PatternValues::LightHorizontal,
-            Pattern::LightVertical => PatternValues::LightVertical,
-            Pattern::LightDown => PatternValues::LightDown,
-            Pattern::LightUp => PatternValues::LightUp,
-            Pattern::LightGrid => PatternValues::LightGrid,
-            Pattern::LightTrellis => PatternValues::LightTrellis,
-            Pattern::Gray125 => PatternValues::Gray125,
-            Pattern::Gray0625 => PatternValues::Gray0625,
+// conversions into umya-spreadsheet values
+#[cfg(feature = "xlsx")]
+mod umya {
+    use super::super::{
+        record::xf::{HorizAlign, Pattern, VertAlign},
+        workbook::{Operator, PanePosition, SheetState, ValidationKind},
+    };
+    use umya_spreadsheet::{
+        ConditionalFormattingOperatorValues, DataValidationOperatorValues, DataValidationValues,
+        HorizontalAlignmentValues, PaneValues, PatternValues, SheetStateValues,
+        VerticalAlignmentValues,
+    };
+
+    impl From<HorizAlign> for HorizontalAlignmentValues {
+        fn from(align: HorizAlign) -> HorizontalAlignmentValues {
+            match align {
+                HorizAlign::General => HorizontalAlignmentValues::General,
+                HorizAlign::Left => HorizontalAlignmentValues::Left,
+                HorizAlign::Center => HorizontalAlignmentValues::Center,
+                HorizAlign::Right => HorizontalAlignmentValues::Right,
+                HorizAlign::Fill => HorizontalAlignmentValues::Fill,
+                HorizAlign::Justify => HorizontalAlignmentValues::Justify,
+                HorizAlign::CenterContinuous => HorizontalAlignmentValues::CenterContinuous,
+                HorizAlign::Distributed => HorizontalAlignmentValues::Distributed,
+            }
+        }
+    }
+
+    impl From<VertAlign> for VerticalAlignmentValues {
+        fn from(align: VertAlign) -> VerticalAlignmentValues {
+            match align {
+                VertAlign::Top => VerticalAlignmentValues::Top,
+                VertAlign::Center => VerticalAlignmentValues::Center,
+                VertAlign::Bottom => VerticalAlignmentValues::Bottom,
+                VertAlign::Justify => VerticalAlignmentValues::Justify,
+                VertAlign::Distributed => VerticalAlignmentValues::Distributed,
+            }
+        }
+    }
+
+    impl From<Pattern> for PatternValues {
+        fn from(pattern: Pattern) -> PatternValues {
+            match pattern {
+                Pattern::None => PatternValues::None,
+                Pattern::Solid => PatternValues::Solid,
+                Pattern::MediumGray => PatternValues::MediumGray,
+                Pattern::DarkGray => PatternValues::DarkGray,
+                Pattern::LightGray => PatternValues::LightGray,
+                Pattern::DarkHorizontal => PatternValues::DarkHorizontal,
+                Pattern::DarkVertical => PatternValues::DarkVertical,
+                Pattern::DarkDown => PatternValues::DarkDown,
+                Pattern::DarkUp => PatternValues::DarkUp,
+                Pattern::DarkGrid => PatternValues::DarkGrid,
+                Pattern::DarkTrellis => PatternValues::DarkTrellis,
+                Pattern::LightHorizontal => PatternValues::LightHorizontal,
+                Pattern::LightVertical => PatternValues::LightVertical,
+                Pattern::LightDown => PatternValues::LightDown,
+                Pattern::LightUp => PatternValues::LightUp,
+                Pattern::LightGrid => PatternValues::LightGrid,
+                Pattern::LightTrellis => PatternValues::LightTrellis,
+                Pattern::Gray125 => PatternValues::Gray125,
+                Pattern::Gray0625 => PatternValues::Gray0625,
+            }
+        }
+    }
+
+    impl From<SheetState> for SheetStateValues {
+        fn from(state: SheetState) -> SheetStateValues {
+            match state {
+                SheetState::Visible => SheetStateValues::Visible,
+                SheetState::Hidden => SheetStateValues::Hidden,
+                SheetState::VeryHidden => SheetStateValues::VeryHidden,
+            }
+        }
+    }
+
+    impl From<PanePosition> for PaneValues {
+        fn from(position: PanePosition) -> PaneValues {
+            match position {
+                PanePosition::BottomRight => PaneValues::BottomRight,
+                PanePosition::TopRight => PaneValues::TopRight,
+                PanePosition::BottomLeft => PaneValues::BottomLeft,
+                PanePosition::TopLeft => PaneValues::TopLeft,
+            }
+        }
+    }
+
+    impl From<Operator> for ConditionalFormattingOperatorValues {
+        fn from(operator: Operator) -> ConditionalFormattingOperatorValues {
+            match operator {
+                Operator::Between => ConditionalFormattingOperatorValues::Between,
+                Operator::NotBetween => ConditionalFormattingOperatorValues::NotBetween,
+                Operator::Equal => ConditionalFormattingOperatorValues::Equal,
+                Operator::NotEqual => ConditionalFormattingOperatorValues::NotEqual,
+                Operator::GreaterThan => ConditionalFormattingOperatorValues::GreaterThan,
+                Operator::LessThan => ConditionalFormattingOperatorValues::LessThan,
+                Operator::GreaterThanOrEqual => {
+                    ConditionalFormattingOperatorValues::GreaterThanOrEqual
+                }
+                Operator::LessThanOrEqual => ConditionalFormattingOperatorValues::LessThanOrEqual,
+            }
+        }
+    }
+
+    impl From<Operator> for DataValidationOperatorValues {
+        fn from(operator: Operator) -> DataValidationOperatorValues {
+            match operator {
+                Operator::Between => DataValidationOperatorValues::Between,
+                Operator::NotBetween => DataValidationOperatorValues::NotBetween,
+                Operator::Equal => DataValidationOperatorValues::Equal,
+                Operator::NotEqual => DataValidationOperatorValues::NotEqual,
+                Operator::GreaterThan => DataValidationOperatorValues::GreaterThan,
+                Operator::LessThan => DataValidationOperatorValues::LessThan,
+                Operator::GreaterThanOrEqual => DataValidationOperatorValues::GreaterThanOrEqual,
+                Operator::LessThanOrEqual => DataValidationOperatorValues::LessThanOrEqual,
+            }
+        }
+    }
+
+    impl From<ValidationKind> for DataValidationValues {
+        fn from(kind: ValidationKind) -> DataValidationValues {
+            match kind {
+                ValidationKind::Any => DataValidationValues::None,
+                ValidationKind::Whole => DataValidationValues::Whole,
+                ValidationKind::Decimal => DataValidationValues::Decimal,
+                ValidationKind::List => DataValidationValues::List,
+                ValidationKind::Date => DataValidationValues::Date,
+                ValidationKind::Time => DataValidationValues::Time,
+                ValidationKind::TextLength => DataValidationValues::TextLength,
+                ValidationKind::Custom => DataValidationValues::Custom,
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_date_format;
+
+    #[test]
+    fn date_formats() {
+        assert!(is_date_format("yyyy-mm-dd"));
+        assert!(is_date_format("[$-409]h:mm AM/PM"));
+        assert!(is_date_format("[h]:mm:ss"));
+        assert!(is_date_format(r#"d "de" mmmm"#));
+    }
+
+    #[test]
+    fn number_formats() {
+        assert!(!is_date_format("#,##0;[Red]-#,##0"));
+        assert!(!is_date_format(r#"0 "days""#));
+        assert!(!is_date_format(r#"0.00 "pcs""#));
+        assert!(!is_date_format("$#,##0_);($#,##0)"));
+        assert!(!is_date_format(r"0\h"));
+        assert!(!is_date_format("[>=100]0;0.00"));
+    }
+}
@@ -0,0 +1,1980 @@
+use super::{
+    crypto::Decryptor,
+    error::{Error, Result},
+    formula,
+    model::{Global, Shared},
+    record::{
+        self, Record, RecordReader, autofilter, autofilter12,
+        bof::StreamType,
+        condfmt::CondFmtStructure,
+        dxfn::{Dxfn, NumFmt},
+        formula::FormulaValue,
+        sst::SstString,
+        style::{self, Style},
+        xf::BorderStyle,
+    },
+    workbook::{
+        AutoFilter, BorderLine, Cell, CellRange, CellStyle, CellValue, Condition,
+        ConditionalFormat, ConditionalRule, DataValidation, DefinedName, DifferentialFormat,
+        ErrorKind, ErrorStyle, FilterColumn, FilterCondition, FilterCriteria, FormulaError,
+        Hyperlink, Note, Operator, OutlineRange, Pane, PanePosition, Row, Sheet, SheetInfo,
+        SheetType, TextFormat, TextRun, ValidationKind, Workbook,
+    },
+};
+use encoding_rs::{Encoding, UTF_16LE};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::File,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+/// Reader parsing the globals substream up front, and each worksheet substream on demand.
+pub struct WorkbookReader<R> {
+    // the stream only holds a weak reference to the compound file
+    _compound_file: cfb::CompoundFile<R>,
+    stream: Stream<R>,
+    global: Global,
+    encoding: &'static Encoding,
+    sst: Vec<SstString>,
+    sheets: Vec<SheetInfo>,
+    defined_names: Vec<DefinedName>,
+    styles: Vec<CellStyle>,
+    // sheet shown when the workbook is opened, from the first Window1 record
+    active_tab: Option<u16>,
+    // whether the Blank and MulBlank records are skipped
+    skip_blanks: bool,
+}
+
+impl WorkbookReader<File> {
+    /// Opens an XLS file and reads its globals substream.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XLS file to open
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WorkbookReader<File>>` - Reader over the file on success, or an error on failure
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(File::open(path)?)
+    }
+
+    /// Opens an encrypted XLS file and reads its globals substream.
+    ///
+    /// The default password `VelvetSweatshop` is tried when `password` doesn't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XLS file to open
+    /// * `password` - Password to open the workbook
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WorkbookReader<File>>` - Reader over the file on success, or an error on failure
+    pub fn open_with_password(path: impl AsRef<Path>, password: &str) -> Result<Self> {
+        Self::new_with_password(File::open(path)?, password)
+    }
+}
+
+impl<R: Read + Seek> WorkbookReader<R> {
+    /// Reads the globals substream of XLS content from any reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WorkbookReader<R>>` - Reader over the content on success, or an error on failure
+    pub fn new(reader: R) -> Result<Self> {
+        Self::read(reader, None)
+    }
+
+    /// Reads the globals substream of encrypted XLS content from any reader.
+    ///
+    /// The default password `VelvetSweatshop` is tried when `password` doesn't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+    /// * `password` - Password to open the workbook
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WorkbookReader<R>>` - Reader over the content on success, or an error on failure
+    pub fn new_with_password(reader: R, password: &str) -> Result<Self> {
+        Self::read(reader, Some(password))
+    }
+
+    fn read(reader: R, password: Option<&str>) -> Result<Self> {
+        let mut compound_file = cfb::CompoundFile::open(reader)?;
+        let stream = Stream::Plain(compound_file.open_stream("/Workbook")?);
+
+        let mut sheets = Vec::new();
+        let mut encoding = UTF_16LE;
+        let mut sst = Vec::new();
+        let mut styles = Vec::new();
+        let mut global = Global::default();
+        let mut active_tab = None;
+
+        #[cfg(feature = "tracing")]
+        let mut book_ignores = HashMap::new();
+
+        let mut records = RecordReader::new(stream);
+        while let Some(record) = records.next() {
+            let record = record?;
+
+            #[cfg(feature = "tracing")]
+            let rname = &record.to_string();
+
+            match record {
+                Record::Bof(data) if !matches!(data.stream_type, StreamType::Workbook) => {
+                    let error = Error::StreamType {
+                        expect: StreamType::Workbook,
+                        actual: data.stream_type,
+                    };
+
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("Workbook [{}] {}\n", rname, error);
+
+                    return Err(error);
+                }
+                Record::Bof(_data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, _data);
+                }
+                Record::Boundsheet8(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.sheet_names.push(data.name.clone());
+                    sheets.push(SheetInfo {
+                        name: data.name,
+                        state: data.state.state().into(),
+                        r#type: data.r#type.into(),
+                        pos: data.pos,
+                    });
+                }
+                Record::CodePage(mut data) => {
+                    data.decode();
+                    let Some(enc) = data.encoding else {
+                        let error = Error::CodePage(data.value);
+
+                        #[cfg(feature = "tracing")]
+                        tracing::error!("Workbook [{}] {}\n", rname, error);
+
+                        return Err(error);
+                    };
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    encoding = enc;
+                }
+                Record::Date1904(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.date1904 = data.is1904;
+                }
+                Record::ExternName(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    // belongs to the preceding SupBook
+                    if let Some(supbook) = global.supbooks.last_mut() {
+                        supbook.names.push(data.name);
+                    }
+                }
+                Record::ExternSheet(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.xtis.extend(data.xtis);
+                }
+                Record::FilePass(data) => {
+                    let encryption = data.decode()?;
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, encryption);
+
+                    let decryptor = Decryptor::new(&encryption, password)?;
+
+                    // the following records are read from the decrypted stream
+                    let mut stream = records.into_inner();
+                    let pos = stream.stream_position()?;
+                    let mut bytes = Vec::new();
+                    stream.seek(SeekFrom::Start(0))?;
+                    stream.read_to_end(&mut bytes)?;
+                    decryptor.decrypt(&mut bytes);
+
+                    let mut cursor = Cursor::new(bytes);
+                    cursor.set_position(pos);
+                    records = RecordReader::new(Stream::Decrypted(cursor));
+                }
+                Record::Font(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    {
+                        let ifnt = if global.fonts.len() < 4 {
+                            global.fonts.len()
+                        } else {
+                            global.fonts.len() + 1
+                        };
+                        tracing::info!("Workbook [{}] ifnt: {}, {:?}\n", rname, ifnt, data);
+                    }
+
+                    global.fonts.push(data);
+                }
+                Record::Format(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.formats.insert(data.ifmt, data.code);
+                }
+                Record::Lbl(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.names.push(data);
+                }
+                Record::Palette(mut data) => {
+                    data.decode();
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.palette.replace(data.colors);
+                }
+                Record::Sst(mut data) => {
+                    data.decode(encoding)?;
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    sst.extend(data.strs);
+                }
+                Record::Style(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    styles.push(cell_style(data));
+                }
+                Record::SupBook(mut data) => {
+                    data.decode(encoding);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.supbooks.push(data);
+                }
+                Record::Window1(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    active_tab.get_or_insert(data.itab_cur);
+                }
+                Record::XF(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(
+                        "Workbook [{}] ixfe: {}, {:?}\n",
+                        rname,
+                        global.xfs.len(),
+                        data
+                    );
+                    global.xfs.push(data);
+                }
+                Record::XFExt(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, data);
+
+                    global.xf_exts.insert(data.ixfe, data.exts);
+                }
+                Record::Theme(data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!(
+                        "Workbook [{}] version: {}, {} bytes\n",
+                        rname,
+                        data.version,
+                        data.content.len()
+                    );
+
+                    global.theme = (!data.content.is_empty()).then_some(data.content);
+                }
+                Record::Eof(_data) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::info!("Workbook [{}] {:?}\n", rname, _data);
+
+                    break;
+                }
+                Record::Ignore(_data) => {
+                    #[cfg(feature = "tracing")]
+                    {
+                        *book_ignores.entry(_data.r#type).or_insert(0) += 1;
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            use super::record::RECORDS;
+            tracing::info!("\n");
+            for (k, v) in book_ignores {
+                let Some(name) = RECORDS.get(&k) else {
+                    continue;
+                };
+                #[cfg(feature = "tracing")]
+                tracing::info!("Workbook [Ignored] occurs:{:2}, 0x{:04X} - {}", v, k, name);
+            }
+            tracing::info!("\n");
+        }
+
+        let defined_names = defined_names(&global, &sheets);
+
+        Ok(WorkbookReader {
+            _compound_file: compound_file,
+            stream: records.into_inner(),
+            global,
+            encoding,
+            sst,
+            sheets,
+            defined_names,
+            styles,
+            active_tab,
+            skip_blanks: false,
+        })
+    }
+
+    /// Skips the cells without a value, only holding a format, for the consumers only reading the values.
+    ///
+    /// # Arguments
+    ///
+    /// * `skip` - Whether the cells of the BLANK and MULBLANK records are skipped, `false` by default
+    ///
+    /// # Returns
+    ///
+    /// * `WorkbookReader<R>` - The same reader, with the option set
+    pub fn skip_blanks(mut self, skip: bool) -> Self {
+        self.skip_blanks = skip;
+        self
+    }
+
+    /// Names of all sheets, in the order of the workbook.
+    pub fn sheet_names(&self) -> impl Iterator<Item = &str> {
+        self.sheets.iter().map(|info| info.name.as_str())
+    }
+
+    /// Metadata of all sheets, in the order of the workbook.
+    pub fn sheets(&self) -> &[SheetInfo] {
+        &self.sheets
+    }
+
+    /// Named ranges and formulas, including print areas, print titles and filter ranges.
+    pub fn defined_names(&self) -> &[DefinedName] {
+        &self.defined_names
+    }
+
+    /// Named cell styles, built-in and user-defined.
+    pub fn styles(&self) -> &[CellStyle] {
+        &self.styles
+    }
+
+    /// Named cell style of an XF, the style itself for a style XF, or the parent style of a cell XF.
+    ///
+    /// # Arguments
+    ///
+    /// * `xf` - Index of the XF record, e.g. `Cell::xf`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&CellStyle>` - Named cell style, or `None` if the XF doesn't exist or has no style
+    pub fn cell_style(&self, xf: u16) -> Option<&CellStyle> {
+        let ixfe = self.global.style_xf(xf)?;
+        self.styles.iter().find(|style| style.xf == ixfe)
+    }
+
+    /// Sheet shown when the workbook is opened.
+    pub fn active_sheet(&self) -> Option<&SheetInfo> {
+        self.active_tab
+            .and_then(|itab| self.sheets.get(itab as usize))
+    }
+
+    /// Parses the worksheet with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the worksheet
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Sheet>` - Worksheet on success, or an error if it doesn't exist or isn't a worksheet
+    pub fn sheet(&mut self, name: &str) -> Result<Sheet> {
+        let index = self
+            .sheets
+            .iter()
+            .position(|info| info.name == name)
+            .ok_or_else(|| Error::Sheet(name.to_string()))?;
+        self.sheet_by_index(index)
+    }
+
+    /// Parses the worksheet at the given position of the workbook.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Zero-based position of the sheet, as in `sheets()`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Sheet>` - Worksheet on success, or an error if it doesn't exist or isn't a worksheet
+    pub fn sheet_by_index(&mut self, index: usize) -> Result<Sheet> {
+        let mut rows = self.rows_by_index(index)?;
+
+        let mut cells = BTreeMap::new();
+        for row in &mut rows {
+            for cell in row?.cells {
+                cells.insert((cell.row, cell.col), cell);
+            }
+        }
+
+        let mut sheet = rows.parser.sheet;
+        sheet.cells = cells;
+        Ok(sheet)
+    }
+
+    /// Iterates over the rows of the worksheet with the given name,
+    /// parsing its substream as it goes instead of keeping the whole sheet in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the worksheet
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Rows<'_, R>>` - Iterator over the rows on success, or an error if it doesn't exist
+    pub fn rows(&mut self, name: &str) -> Result<Rows<'_, R>> {
+        let index = self
+            .sheets
+            .iter()
+            .position(|info| info.name == name)
+            .ok_or_else(|| Error::Sheet(name.to_string()))?;
+        self.rows_by_index(index)
+    }
+
+    /// Iterates over the rows of the worksheet at the given position of the workbook,
+    /// parsing its substream as it goes instead of keeping the whole sheet in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Zero-based position of the sheet, as in `sheets()`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Rows<'_, R>>` - Iterator over the rows on success, or an error if it doesn't exist
+    pub fn rows_by_index(&mut self, index: usize) -> Result<Rows<'_, R>> {
+        let Some(info) = self.sheets.get(index) else {
+            return Err(Error::Sheet(index.to_string()));
+        };
+
+        self.stream.seek(SeekFrom::Start(info.pos as u64))?;
+        let sheet = Sheet::new(info.name.clone(), info.state);
+
+        Ok(Rows {
+            records: RecordReader::new(&mut self.stream),
+            parser: SheetParser::new(
+                &self.global,
+                self.encoding,
+                &self.sst,
+                sheet,
+                self.skip_blanks,
+            ),
+            done: false,
+        })
+    }
+
+    /// Parses every worksheet, skipping the other sheet types.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook on success, or an error on failure
+    pub fn into_workbook(mut self) -> Result<Workbook> {
+        let mut sheets = Vec::new();
+        let mut active = None;
+        for index in 0..self.sheets.len() {
+            if self.sheets[index].r#type == SheetType::Worksheet {
+                if self.active_tab == Some(index as u16) {
+                    active = Some(sheets.len());
+                }
+                sheets.push(self.sheet_by_index(index)?);
+            }
+        }
+
+        Ok(Workbook {
+            global: self.global,
+            sheets,
+            defined_names: self.defined_names,
+            styles: self.styles,
+            active,
+        })
+    }
+}
+
+/// Decompiles the formulas of the LBL records, once all of them are known.
+fn defined_names(global: &Global, sheets: &[SheetInfo]) -> Vec<DefinedName> {
+    let mut defined_names = Vec::new();
+    for lbl in &global.names {
+        // names are not used from a cell, relative references are kept relative to A1
+        let formula = match formula::decompile(global, &lbl.rgce, &lbl.rgcb, 0, 0) {
+            Ok(formula) => formula,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Workbook [Lbl] {:?}, {}\n", lbl, _error);
+
+                continue;
+            }
+        };
+        defined_names.push(DefinedName {
+            name: lbl.name.clone(),
+            sheet: (lbl.itab as usize)
+                .checked_sub(1)
+                .and_then(|index| sheets.get(index))
+                .map(|info| info.name.clone()),
+            hidden: lbl.hidden,
+            builtin: lbl.builtin,
+            formula,
+        });
+    }
+    defined_names
+}
+
+/// Named cell style of a STYLE record, the built-in styles without STYLEEXT record are named after their identifier.
+fn cell_style(data: style::Data) -> CellStyle {
+    let (builtin, outline_level, name) = match data.style {
+        Style::BuiltIn { isbi, outlevel } => (
+            Some(isbi),
+            outlevel.level(),
+            builtin_style_name(isbi, outlevel.level()),
+        ),
+        Style::User { name, .. } => (None, None, name),
+    };
+    CellStyle {
+        name: match &data.ext {
+            Some(ext) if !ext.name.is_empty() => ext.name.clone(),
+            _ => name,
+        },
+        xf: data.ixfe,
+        builtin,
+        outline_level,
+        hidden: data.ext.as_ref().is_some_and(|ext| ext.hidden),
+        category: data.ext.map(|ext| ext.category.into()),
+    }
+}
+
+// names of the built-in styles of XLSX, by identifier
+fn builtin_style_name(isbi: u8, level: Option<u8>) -> String {
+    const NAMES: [&str; 54] = [
+        "Normal",
+        "RowLevel_",
+        "ColLevel_",
+        "Comma",
+        "Currency",
+        "Percent",
+        "Comma [0]",
+        "Currency [0]",
+        "Hyperlink",
+        "Followed Hyperlink",
+        "Note",
+        "Warning Text",
+        "Emphasis 1",
+        "Emphasis 2",
+        "Emphasis 3",
+        "Title",
+        "Heading 1",
+        "Heading 2",
+        "Heading 3",
+        "Heading 4",
+        "Input",
+        "Output",
+        "Calculation",
+        "Check Cell",
+        "Linked Cell",
+        "Total",
+        "Good",
+        "Bad",
+        "Neutral",
+        "Accent1",
+        "20% - Accent1",
+        "40% - Accent1",
+        "60% - Accent1",
+        "Accent2",
+        "20% - Accent2",
+        "40% - Accent2",
+        "60% - Accent2",
+        "Accent3",
+        "20% - Accent3",
+        "40% - Accent3",
+        "60% - Accent3",
+        "Accent4",
+        "20% - Accent4",
+        "40% - Accent4",
+        "60% - Accent4",
+        "Accent5",
+        "20% - Accent5",
+        "40% - Accent5",
+        "60% - Accent5",
+        "Accent6",
+        "20% - Accent6",
+        "40% - Accent6",
+        "60% - Accent6",
+        "Explanatory Text",
+    ];
+    match (NAMES.get(isbi as usize), level) {
+        // the outline level is one-based in the name
+        (Some(name), Some(level)) => format!("{}{}", name, level + 1),
+        (Some(name), None) => name.to_string(),
+        (None, _) => format!("Style {}", isbi),
+    }
+}
+
+/// Range of the auto filter of a worksheet, from its `_FilterDatabase` name.
+fn filter_database(global: &Global, sheet_name: &str) -> Option<CellRange> {
+    let lbl = global.names.iter().find(|lbl| {
+        lbl.builtin
+            && lbl.name == "_xlnm._FilterDatabase"
+            && (lbl.itab as usize)
+                .checked_sub(1)
+                .and_then(|index| global.sheet_names.get(index))
+                .is_some_and(|name| name == sheet_name)
+    })?;
+
+    // PtgArea3d or PtgRef3d, of any class
+    let u16_at = |i: usize| {
+        Some(u16::from_le_bytes([
+            *lbl.rgce.get(i)?,
+            *lbl.rgce.get(i + 1)?,
+        ]))
+    };
+    match lbl.rgce.first()? & 0x9F {
+        0x1B => Some(CellRange {
+            row_first: u16_at(3)?,
+            row_last: u16_at(5)?,
+            col_first: u16_at(7)? & 0x3FFF,
+            col_last: u16_at(9)? & 0x3FFF,
+        }),
+        0x1A => Some(CellRange {
+            row_first: u16_at(3)?,
+            row_last: u16_at(3)?,
+            col_first: u16_at(5)? & 0x3FFF,
+            col_last: u16_at(5)? & 0x3FFF,
+        }),
+        _ => None,
+    }
+}
+
+fn filter_column(filter: &autofilter::Filter) -> FilterColumn {
+    let info = &filter.info;
+    let criteria = if info.top10() {
+        FilterCriteria::Top10 {
+            top: info.top(),
+            percent: info.percent(),
+            value: info.top_n(),
+        }
+    } else {
+        let conditions: Vec<FilterCondition> = [
+            (&filter.doper1, &filter.value1),
+            (&filter.doper2, &filter.value2),
+        ]
+        .into_iter()
+        .filter_map(|(doper, value)| {
+            Some(FilterCondition {
+                operator: Operator::from_grbit_sign(doper.grbit_sign)?,
+                value: value.clone()?,
+            })
+        })
+        .collect();
+
+        // values selected in the list are stored as equal conditions
+        if info.simple1() && (conditions.len() < 2 || info.simple2()) {
+            FilterCriteria::Values(conditions.into_iter().map(|c| c.value).collect())
+        } else {
+            FilterCriteria::Custom {
+                and: info.join() == 0,
+                conditions,
+            }
+        }
+    };
+
+    FilterColumn {
+        col: filter.i_entry,
+        criteria,
+    }
+}
+
+/// Criteria of an AUTOFILTER12 record, None for the filters by color or icon.
+fn filter_column12(data: &autofilter12::Data) -> Option<FilterColumn> {
+    if data.ft != 0 || data.criteria.is_empty() {
+        return None;
+    }
+    let conditions: Vec<FilterCondition> = data
+        .criteria
+        .iter()
+        .filter_map(|(doper, value)| {
+            Some(FilterCondition {
+                operator: Operator::from_grbit_sign(doper.grbit_sign)?,
+                value: value.clone()?,
+            })
+        })
+        .collect();
+
+    let criteria = if conditions.iter().all(|c| c.operator == Operator::Equal) {
+        FilterCriteria::Values(conditions.into_iter().map(|c| c.value).collect())
+    } else {
+        FilterCriteria::Custom {
+            and: false,
+            conditions,
+        }
+    };
+    Some(FilterColumn {
+        col: data.i_entry,
+        criteria,
+    })
+}
+
+fn differential_format(global: &Global, dxf: &Dxfn) -> DifferentialFormat {
+    let mut format = DifferentialFormat::default();
+    let flags = &dxf.flags;
+
+    match &dxf.num_fmt {
+        Some(NumFmt::Code(code)) => format.number_format = Some(code.clone()),
+        Some(NumFmt::Index(ifmt)) => match global.formats.get(&(*ifmt).into()) {
+            Some(code) => format.number_format = Some(code.clone()),
+            None => format.number_format_id = Some((*ifmt).into()),
+        },
+        None => (),
+    }
+
+    if let Some(font) = &dxf.font {
+        // -1 when unchanged
+        format.font_height = u16::try_from(font.height).ok();
+        format.font_color = u16::try_from(font.icv).ok();
+        format.bold = (!font.bls_ninch).then_some(font.bls >= 700);
+        format.italic = (!font.italic_ninch).then_some(font.italic);
+        format.strikeout = (!font.strike_out_ninch).then_some(font.strike_out);
+        format.underline = (!font.uls_ninch).then_some(font.uls);
+    }
+
+    if let Some(fill) = &dxf.fill {
+        if !flags.fls_ninch() {
+            format.fill_pattern = fill.pattern_or_err().ok().map(|pattern| pattern as u8);
+        }
+        format.fill_fore_color = (!flags.icv_f_ninch()).then_some(fill.fore_icv().into());
+        format.fill_back_color = (!flags.icv_b_ninch()).then_some(fill.back_icv().into());
+    }
+
+    if let Some(borders) = &dxf.borders {
+        let line = |ninch: bool, style: Option<BorderStyle>, icv: u8| {
+            (!ninch).then_some(BorderLine {
+                style: style.map_or(0, |style| style as u8),
+                color: icv.into(),
+            })
+        };
+        format.border_left = line(
+            flags.left_ninch(),
+            borders.left_style_or_err().ok(),
+            borders.left_icv(),
+        );
+        format.border_right = line(
+            flags.right_ninch(),
+            borders.right_style_or_err().ok(),
+            borders.right_icv(),
+        );
+        format.border_top = line(
+            flags.top_ninch(),
+            borders.top_style_or_err().ok(),
+            borders.top_icv(),
+        );
+        format.border_bottom = line(
+            flags.bottom_ninch(),
+            borders.bottom_style_or_err().ok(),
+            borders.bottom_icv(),
+        );
+    }
+
+    format
+}
+
+/// Workbook stream, read into memory and decrypted when protected by a FilePass record.
+enum Stream<R> {
+    Plain(cfb::Stream<R>),
+    Decrypted(Cursor<Vec<u8>>),
+}
+
+impl<R: Read + Seek> Read for Stream<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Plain(stream) => stream.read(buf),
+            Stream::Decrypted(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl<R: Read + Seek> Seek for Stream<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Stream::Plain(stream) => stream.seek(pos),
+            Stream::Decrypted(stream) => stream.seek(pos),
+        }
+    }
+}
+
+/// Iterator over the rows of a worksheet, in row order.
+///
+/// Cells are buffered per row block, which ends with a DBCell record or the Row records
+/// of the next block, so at most 32 rows are held in memory for the files written by Excel.
+pub struct Rows<'a, R> {
+    records: RecordReader<&'a mut Stream<R>>,
+    parser: SheetParser<'a>,
+    done: bool,
+}
+
+impl<R> Rows<'_, R> {
+    /// Formulas of the rows yielded so far that couldn't be decompiled.
+    pub fn formula_errors(&self) -> &[FormulaError] {
+        &self.parser.sheet.formula_errors
+    }
+}
+
+impl<R: Read + Seek> Iterator for Rows<'_, R> {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.parser.ready.pop_front() {
+                return Some(Ok(row));
+            }
+            if self.done {
+                return None;
+            }
+
+            let result = match self.records.next() {
+                Some(Ok(record)) => self.parser.handle(record),
+                Some(Err(error)) => Err(error),
+                // stream ended without EOF record
+                None => {
+                    self.done = true;
+                    self.parser.flush();
+                    continue;
+                }
+            };
+            if let Err(error) = result {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+/// Worksheet substream state, fed one record at a time.
+struct SheetParser<'a> {
+    global: &'a Global,
+    encoding: &'static Encoding,
+    sst: &'a [SstString],
+    sheet: Sheet,
+    // cells of the rows not yielded yet
+    pending: BTreeMap<(u16, u16), Cell>,
+    // formula cell whose cached string result is in the next String record
+    string_formula: Option<(u16, u16)>,
+    // formulas of the pending cells, decompiled when their rows are yielded, as ShrFmla follows its first Formula
+    formulas: Vec<record::formula::Data>,
+    // comment object whose text is in the next TxO record
+    comment_obj: Option<u16>,
+    // texts of the comment objects, by object id
+    comments: HashMap<u16, (String, Vec<TextRun>)>,
+    // whether the panes of the next Pane record are frozen, from Window2
+    frozen: bool,
+    // whether rows are hidden by the auto filter, from FilterMode
+    filter_mode: bool,
+    // whether the Blank and MulBlank records are skipped
+    skip_blanks: bool,
+    // CondFmt record the next CF records belong to
+    cond_fmt_id: u16,
+    // positions of the rules of the CF records by CondFmt record, None for the unsupported ones, for CFEx
+    cf_rules: HashMap<u16, Vec<Option<(usize, usize)>>>,
+    ready: VecDeque<Row>,
+    #[cfg(feature = "tracing")]
+    ignores: HashMap<u16, usize>,
+}
+
+impl<'a> SheetParser<'a> {
+    fn new(
+        global: &'a Global,
+        encoding: &'static Encoding,
+        sst: &'a [SstString],
+        sheet: Sheet,
+        skip_blanks: bool,
+    ) -> Self {
+        SheetParser {
+            global,
+            encoding,
+            sst,
+            sheet,
+            pending: BTreeMap::new(),
+            string_formula: None,
+            formulas: Vec::new(),
+            comment_obj: None,
+            comments: HashMap::new(),
+            frozen: false,
+            filter_mode: false,
+            skip_blanks,
+            cond_fmt_id: 0,
+            cf_rules: HashMap::new(),
+            ready: VecDeque::new(),
+            #[cfg(feature = "tracing")]
+            ignores: HashMap::new(),
+        }
+    }
+
+    fn handle(&mut self, record: Record) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let rname = &record.to_string();
+
+        match record {
+            Record::Bof(data) if !matches!(data.stream_type, StreamType::Worksheet) => {
+                let error = Error::StreamType {
+                    expect: StreamType::Worksheet,
+                    actual: data.stream_type,
+                };
+
+                #[cfg(feature = "tracing")]
+                tracing::error!("{} [{}] {}\n", self.sheet.name, rname, error);
+
+                return Err(error);
+            }
+            Record::Bof(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+            }
+            Record::Array(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let anchor = (data.r#ref.row_first, data.r#ref.col_first.into());
+                self.sheet.shared.insert(anchor, Shared::Array(data));
+            }
+            Record::AutoFilter(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                if let Some(auto_filter) = self.sheet.auto_filter.as_mut() {
+                    auto_filter.columns.push(filter_column(&data.filter));
+                }
+            }
+            Record::AutoFilter12(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // lists of more than two values replace the conditions of the AutoFilter record
+                if let Some(auto_filter) = self.sheet.auto_filter.as_mut()
+                    && let Some(column) = filter_column12(&data)
+                {
+                    match auto_filter.columns.iter_mut().find(|c| c.col == column.col) {
+                        Some(existing) => *existing = column,
+                        None => auto_filter.columns.push(column),
+                    }
+                }
+            }
+            Record::AutoFilterInfo(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+
+                // the range is only stored in the name
+                self.sheet.auto_filter =
+                    filter_database(self.global, &self.sheet.name).map(|range| AutoFilter {
+                        range,
+                        columns: Vec::new(),
+                        filtered: self.filter_mode,
+                    });
+            }
+            Record::Blank(_data) if self.skip_blanks => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+            }
+            Record::Blank(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.insert_cell(data.row, data.col, data.ixfe, CellValue::Empty);
+            }
+            Record::BoolErr(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let value = if data.is_err {
+                    CellValue::Error(ErrorKind::from_code(data.boolerr))
+                } else {
+                    CellValue::Bool(data.boolerr == 0x01)
+                };
+                self.insert_cell(data.row, data.col, data.ixfe, value);
+            }
+            Record::BottomMargin(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.margins.bottom = data.num;
+            }
+            Record::CF(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let rule = &data.rule;
+                let position = self.push_rule(
+                    rule.ct,
+                    rule.cp,
+                    [&rule.rgce1, &rule.rgce2],
+                    Some(&rule.dxf),
+                );
+                let rules = self.cf_rules.entry(self.cond_fmt_id).or_default();
+                rules.push(position);
+            }
+            Record::CF12(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let rule = &data.rule;
+                let position = self.push_rule(
+                    rule.ct,
+                    rule.cp,
+                    [&rule.rgce1, &rule.rgce2],
+                    rule.dxf.as_ref(),
+                );
+                if let Some((format, index)) = position {
+                    let xrule = &mut self.sheet.conditional_formats[format].rules[index];
+                    xrule.priority = Some(rule.priority);
+                    xrule.stop_if_true = rule.stop_if_true;
+                }
+            }
+            Record::CFEx(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // extends a CF record of a previous CondFmt record
+                if let Some(content) = &data.content
+                    && let Some(rules) = self.cf_rules.get(&data.id)
+                    && let Some(&Some((format, index))) = rules.get(content.icf as usize)
+                {
+                    let rule = &mut self.sheet.conditional_formats[format].rules[index];
+                    rule.priority = Some(content.priority);
+                    rule.stop_if_true = content.stop_if_true;
+                }
+            }
+            Record::ColInfo(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let info = &data.info;
+                if info.outlevel() > 0 || info.collapsed() {
+                    self.sheet.outline.cols.push(OutlineRange {
+                        first: data.col_min,
+                        last: data.col_max,
+                        level: info.outlevel(),
+                        collapsed: info.collapsed(),
+                    });
+                }
+                self.sheet.colinfos.push(data);
+            }
+            Record::CondFmt(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.cond_fmt_id = data.cf.id;
+                self.push_conditional_format(&data.cf);
+            }
+            Record::CondFmt12(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.push_conditional_format(&data.cf);
+            }
+            Record::DbCell(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+
+                // DBCell closes a row block
+                self.flush();
+            }
+            Record::DefaultColWidth(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.default_col_width = Some(data.width);
+            }
+            Record::DefaultRowHeight(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // the height of hidden rows isn't a default height
+                self.sheet.default_row_height = (!data.info.zero()).then_some(data.height as u16);
+            }
+            Record::Dimensions(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+            }
+            Record::Dv(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.push_data_validation(data);
+            }
+            Record::DVal(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+            }
+            Record::FilterMode(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+
+                self.filter_mode = true;
+            }
+            Record::Formula(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let value = match data.value {
+                    FormulaValue::Number(num) => CellValue::Number(num),
+                    FormulaValue::Bool(b) => CellValue::Bool(b),
+                    FormulaValue::Error(code) => CellValue::Error(ErrorKind::from_code(code)),
+                    FormulaValue::Empty => CellValue::String(String::new()),
+                    FormulaValue::String => {
+                        // wait for the following String record
+                        self.string_formula.replace((data.row, data.col));
+                        CellValue::Empty
+                    }
+                };
+                self.insert_cell(data.row, data.col, data.ixfe, value);
+                self.formulas.push(data);
+            }
+            Record::Footer(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.footer = data.text;
+            }
+            Record::Guts(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // the levels count the ungrouped level
+                let outline = &mut self.sheet.outline;
+                outline.row_level = data.i_level_rw_mac.saturating_sub(1) as u8;
+                outline.col_level = data.i_level_col_mac.saturating_sub(1) as u8;
+            }
+            Record::HCenter(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.center_horizontally = data.value;
+            }
+            Record::Header(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.header = data.text;
+            }
+            Record::HLink(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.hyperlinks.push(Hyperlink {
+                    row_first: data.ref8.row_min,
+                    row_last: data.ref8.row_max,
+                    col_first: data.ref8.col_min,
+                    col_last: data.ref8.col_max,
+                    target: data.target,
+                    location: data.location,
+                    display: data.display,
+                    tooltip: None,
+                });
+            }
+            Record::HLinkTooltip(mut data) => {
+                data.decode();
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // follows the HLINK record of the same range
+                let ref8 = &data.ref8;
+                if let Some(hyperlink) = self.sheet.hyperlinks.iter_mut().rev().find(|h| {
+                    (h.row_first, h.row_last, h.col_first, h.col_last)
+                        == (ref8.row_min, ref8.row_max, ref8.col_min, ref8.col_max)
+                }) {
+                    hyperlink.tooltip.replace(data.tooltip);
+                }
+            }
+            Record::HorizontalPageBreaks(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let breaks = data.breaks.iter().map(|brk| brk.index);
+                self.sheet.page_setup.row_breaks.extend(breaks);
+            }
+            Record::Label(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let value = CellValue::String(data.content);
+                self.insert_cell(data.row, data.col, data.ixfe, value);
+            }
+            Record::LabelSST(data) => {
+                let content = self
+                    .sst
+                    .get(data.isst as usize)
+                    .ok_or(Error::msg("sst get"))?;
+
+                #[cfg(feature = "tracing")]
+                tracing::info!(
+                    "{} [{}] {:?}, content: {:?}\n",
+                    self.sheet.name,
+                    rname,
+                    data,
+                    content
+                );
+
+                let value = CellValue::String(content.text.clone());
+                let cell = self.insert_cell(data.row, data.col, data.ixfe, value);
+                let len = content.text.encode_utf16().count();
+                let runs: Vec<TextRun> = content
+                    .runs
+                    .iter()
+                    .filter(|(ich, _)| (*ich as usize) < len)
+                    .map(|&(start, font)| TextRun { start, font })
+                    .collect();
+                if !runs.is_empty() || content.phonetic.is_some() {
+                    cell.text_format = Some(Box::new(TextFormat {
+                        runs,
+                        phonetic: content.phonetic.clone(),
+                    }));
+                }
+            }
+            Record::LeftMargin(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.margins.left = data.num;
+            }
+            Record::MergeCells(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.merges.extend(data.refs);
+            }
+            Record::MulBlank(_data) if self.skip_blanks => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+            }
+            Record::MulBlank(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                for (col, ixfe) in (data.col_min..).zip(data.ixfs) {
+                    self.insert_cell(data.row, col, ixfe, CellValue::Empty);
+                }
+            }
+            Record::MulRk(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                for (col, (ixfe, num)) in (data.col_min..).zip(data.values) {
+                    let value = CellValue::Number(num);
+                    self.insert_cell(data.row, col, ixfe, value);
+                }
+            }
+            Record::Note(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let (text, runs) = self.comments.remove(&data.id_obj).unwrap_or_default();
+                self.sheet.notes.push(Note {
+                    row: data.row,
+                    col: data.col,
+                    author: data.author,
+                    text,
+                    runs,
+                    visible: data.show,
+                });
+            }
+            Record::Number(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let value = CellValue::Number(data.num);
+                self.insert_cell(data.row, data.col, data.ixfe, value);
+            }
+            Record::Obj(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // only comments are kept
+                self.comment_obj = (data.ot == 0x0019).then_some(data.id);
+            }
+            Record::Pane(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.view.pane.replace(Pane {
+                    frozen: self.frozen,
+                    x: data.x,
+                    y: data.y,
+                    top_row: data.row_top,
+                    left_col: data.col_left,
+                    active: PanePosition::from_pnn(data.pnn_acct),
+                });
+            }
+            Record::PrintGrid(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.print_gridlines = data.value;
+            }
+            Record::PrintRowCol(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.print_headings = data.value;
+            }
+            Record::RightMargin(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.margins.right = data.num;
+            }
+            Record::Rk(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let value = CellValue::Number(data.num);
+                self.insert_cell(data.row, data.col, data.ixfe, value);
+            }
+            Record::RowInfo(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // Row records open a row block, after the cells of the previous one
+                self.flush();
+                let info = &data.info;
+                if info.outlevel() > 0 || info.collapsed() {
+                    self.sheet.outline.rows.push(OutlineRange {
+                        first: data.row,
+                        last: data.row,
+                        level: info.outlevel(),
+                        collapsed: info.collapsed(),
+                    });
+                }
+                self.sheet.rowinfos.push(data);
+            }
+            Record::Scl(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let zoom = (data.nscl as i32 * 100).checked_div(data.dscl as i32);
+                // Excel zooms from 10% to 400%
+                self.sheet.view.zoom = zoom.unwrap_or(100).clamp(10, 400) as u16;
+            }
+            Record::Selection(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                // one record per pane, only the active one is kept
+                let active = self
+                    .sheet
+                    .view
+                    .pane
+                    .map_or(PanePosition::TopLeft, |pane| pane.active);
+                if PanePosition::from_pnn(data.pnn) == active {
+                    self.sheet.view.active_cell = (data.row_act, data.col_act);
+                }
+            }
+            Record::Setup(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let info = &data.info;
+                let page_setup = &mut self.sheet.page_setup;
+                // without printer settings only the fit, page start and header margins are defined
+                if !info.no_pls() {
+                    page_setup.paper_size = Some(data.paper_size);
+                    page_setup.scale = data.scale;
+                    page_setup.landscape = !info.no_orient() && !info.portrait();
+                }
+                page_setup.fit_width = data.fit_width;
+                page_setup.fit_height = data.fit_height;
+                page_setup.first_page_number = info.use_page().then_some(data.page_start);
+                page_setup.margins.header = data.num_hdr;
+                page_setup.margins.footer = data.num_ftr;
+            }
+            Record::ShrFmla(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let anchor = (data.r#ref.row_first, data.r#ref.col_first.into());
+                self.sheet.shared.insert(anchor, Shared::Formula(data));
+            }
+            Record::String(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                if let Some(anchor) = self.string_formula.take()
+                    && let Some(cell) = self.pending.get_mut(&anchor)
+                {
+                    cell.value = CellValue::String(data.content);
+                }
+            }
+            Record::Table(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let anchor = (data.r#ref.row_first, data.r#ref.col_first.into());
+                self.sheet.shared.insert(anchor, Shared::Table(data));
+            }
+            Record::TopMargin(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.margins.top = data.num;
+            }
+            Record::TxO(mut data) => {
+                data.decode(self.encoding);
+
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                if let Some(id) = self.comment_obj.take() {
+                    // the last run only marks the end of the text
+                    let len = data.text.encode_utf16().count();
+                    let runs = data
+                        .runs
+                        .iter()
+                        .filter(|(ich, _)| (*ich as usize) < len)
+                        .map(|&(start, font)| TextRun { start, font })
+                        .collect();
+                    self.comments.insert(id, (data.text, runs));
+                }
+            }
+            Record::VCenter(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.center_vertically = data.value;
+            }
+            Record::VerticalPageBreaks(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let breaks = data.breaks.iter().map(|brk| brk.index);
+                self.sheet.page_setup.col_breaks.extend(breaks);
+            }
+            Record::Window2(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                let info = &data.info;
+                let view = &mut self.sheet.view;
+                view.selected = info.selected();
+                view.show_formulas = info.dsp_fmla();
+                view.show_gridlines = info.dsp_grid();
+                view.show_headers = info.dsp_rw_col();
+                view.show_zeros = info.dsp_zeros();
+                view.right_to_left = info.right_to_left();
+                view.page_break_preview = info.slv();
+                view.top_row = data.row_top;
+                view.left_col = data.col_left;
+                self.frozen = info.frozen();
+            }
+            Record::WsBool(data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, data);
+
+                self.sheet.page_setup.fit_to_page = data.info.fit_to_page();
+                self.sheet.outline.summary_below = data.info.row_sums_below();
+                self.sheet.outline.summary_right = data.info.col_sums_right();
+            }
+            Record::Eof(_data) => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("{} [{}] {:?}\n", self.sheet.name, rname, _data);
+
+                self.flush();
+                // rules of unsupported types are skipped
+                self.sheet
+                    .conditional_formats
+                    .retain(|format| !format.rules.is_empty());
+
+                #[cfg(feature = "tracing")]
+                {
+                    use super::record::RECORDS;
+                    tracing::info!("\n");
+                    for (k, v) in &self.ignores {
+                        let Some(name) = RECORDS.get(k) else {
+                            continue;
+                        };
+                        tracing::info!(
+                            "Worksheet [Ignored] occurs:{:2}, 0x{:04X} - {}",
+                            v,
+                            k,
+                            name
+                        );
+                    }
+                    tracing::info!("\n");
+                }
+            }
+            Record::Ignore(_data) => {
+                #[cfg(feature = "tracing")]
+                {
+                    *self.ignores.entry(_data.r#type).or_insert(0) += 1;
+                }
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn push_conditional_format(&mut self, cf: &CondFmtStructure) {
+        self.sheet.conditional_formats.push(ConditionalFormat {
+            ranges: cf.sqref.iter().map(CellRange::from).collect(),
+            rules: Vec::new(),
+        });
+    }
+
+    fn push_data_validation(&mut self, data: record::dv::Data) {
+        let ranges: Vec<CellRange> = data.sqref.iter().map(CellRange::from).collect();
+
+        // formulas are relative to the top left cell of the ranges
+        let row = ranges.iter().map(|r| r.row_first).min().unwrap_or(0);
+        let col = ranges.iter().map(|r| r.col_first).min().unwrap_or(0);
+        let decompile = |rgce: &[u8]| {
+            (!rgce.is_empty())
+                .then(|| formula::decompile(self.global, rgce, &[], row, col))
+                .transpose()
+        };
+        let formulas = decompile(&data.rgce1).and_then(|f1| Ok((f1, decompile(&data.rgce2)?)));
+        let (mut formula1, formula2) = match formulas {
+            Ok(formulas) => formulas,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("{} [Dv] {}\n", self.sheet.name, _error);
+
+                return;
+            }
+        };
+
+        let info = &data.info;
+        // the items of explicit lists are separated by null characters
+        if info.str_lookup() {
+            formula1 = formula1.map(|list| list.replace('\0', ","));
+        }
+
+        self.sheet.data_validations.push(DataValidation {
+            ranges,
+            kind: ValidationKind::from_val_type(info.val_type()),
+            operator: Operator::from_typ(info.typ_operator()).unwrap_or(Operator::Between),
+            formula1,
+            formula2,
+            allow_blank: info.allow_blank(),
+            show_dropdown: !info.suppress_combo(),
+            show_input_message: info.show_input_msg(),
+            show_error_message: info.show_error_msg(),
+            error_style: ErrorStyle::from_err_style(info.err_style()),
+            prompt_title: data.prompt_title,
+            prompt: data.prompt,
+            error_title: data.error_title,
+            error: data.error,
+        });
+    }
+
+    /// Adds a rule to the last conditional format.
+    ///
+    /// # Arguments
+    ///
+    /// * `ct` - Type of the rule, 1 for a cell value comparison and 2 for a formula
+    /// * `cp` - Comparison operator of cell value rules
+    /// * `rgces` - Formulas of the rule
+    /// * `dxf` - Formatting of the rule
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(usize, usize)>` - Indexes of the format and of the rule, or `None` when the rule is not supported
+    fn push_rule(
+        &mut self,
+        ct: u8,
+        cp: u8,
+        rgces: [&[u8]; 2],
+        dxf: Option<&Dxfn>,
+    ) -> Option<(usize, usize)> {
+        let index = self.sheet.conditional_formats.len().checked_sub(1)?;
+        let format = &self.sheet.conditional_formats[index];
+
+        // formulas are relative to the top left cell of the ranges
+        let row = format.ranges.iter().map(|r| r.row_first).min().unwrap_or(0);
+        let col = format.ranges.iter().map(|r| r.col_first).min().unwrap_or(0);
+        let decompile = |rgce: &[u8]| formula::decompile(self.global, rgce, &[], row, col);
+        let condition = match (ct, Operator::from_cp(cp)) {
+            (1, Some(operator)) => {
+                let two = matches!(operator, Operator::Between | Operator::NotBetween);
+                decompile(rgces[0]).and_then(|formula1| {
+                    Ok(Condition::CellIs {
+                        operator,
+                        formula1,
+                        formula2: two.then(|| decompile(rgces[1])).transpose()?,
+                    })
+                })
+            }
+            (2, _) => decompile(rgces[0]).map(Condition::Expression),
+            // color scales, data bars, top tens and icon sets
+            _ => return None,
+        };
+        let condition = match condition {
+            Ok(condition) => condition,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("{} [CF] {}\n", self.sheet.name, _error);
+
+                return None;
+            }
+        };
+
+        let rule = ConditionalRule {
+            condition,
+            format: dxf
+                .map(|dxf| differential_format(self.global, dxf))
+                .unwrap_or_default(),
+            priority: None,
+            stop_if_true: false,
+        };
+        let rules = &mut self.sheet.conditional_formats[index].rules;
+        rules.push(rule);
+        Some((index, rules.len() - 1))
+    }
+
+    fn insert_cell(&mut self, row: u16, col: u16, xf: u16, value: CellValue) -> &mut Cell {
+        // dates are kept in the 1900 date system
+        let value = match value {
+            CellValue::Number(num) if self.global.is_date(xf.into()) => {
+                CellValue::DateTime(if self.global.date1904 {
+                    num + 1462.0
+                } else {
+                    num
+                })
+            }
+            value => value,
+        };
+        let cell = Cell {
+            row,
+            col,
+            value,
+            xf,
+            text_format: None,
+        };
+        // a later record of the same cell replaces it
+        self.pending.insert((row, col), cell);
+        self.pending.get_mut(&(row, col)).unwrap()
+    }
+
+    /// Moves the pending cells, with their formulas, to the ready rows.
+    fn flush(&mut self) {
+        for data in self.formulas.drain(..) {
+            let text = formula_text(self.global, &data, &self.sheet.shared);
+
+            let text = match text {
+                Ok(text) => text,
+                Err(error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("{} [Formula] {:?}, {}\n", self.sheet.name, data, error);
+
+                    self.sheet.formula_errors.push(FormulaError {
+                        row: data.row,
+                        col: data.col,
+                        message: error.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            if let Some(text) = text
+                && let Some(cell) = self.pending.get_mut(&(data.row, data.col))
+            {
+                let cached = std::mem::replace(&mut cell.value, CellValue::Empty);
+                cell.value = CellValue::Formula {
+                    text,
+                    cached: Box::new(cached),
+                };
+            }
+        }
+
+        for cell in std::mem::take(&mut self.pending).into_values() {
+            match self.ready.back_mut() {
+                Some(row) if row.row == cell.row => row.cells.push(cell),
+                _ => self.ready.push_back(Row {
+                    row: cell.row,
+                    cells: vec![cell],
+                }),
+            }
+        }
+    }
+}
+
+/// Formula text of a FORMULA record, resolving `PtgExp` and `PtgTbl` to their anchor.
+///
+/// # Returns
+///
+/// * `Result<Option<String>>` - None for the cells of an array or a table other than the anchor
+fn formula_text(
+    global: &Global,
+    data: &record::formula::Data,
+    shared: &HashMap<(u16, u16), Shared>,
+) -> Result<Option<String>> {
+    let (row, col) = (data.row, data.col);
+
+    // PtgExp and PtgTbl point to the anchor of a shared, array or table formula
+    let [0x01 | 0x02, r1, r2, c1, c2, ..] = data.rgce[..] else {
+        return formula::decompile(global, &data.rgce, &data.rgcb, row, col).map(Some);
+    };
+    let anchor = (u16::from_le_bytes([r1, r2]), u16::from_le_bytes([c1, c2]));
+    let text = match shared.get(&anchor) {
+        // relative references are shifted by decompiling on this cell
+        Some(Shared::Formula(data)) => {
+            formula::decompile(global, &data.rgce, &data.rgcb, row, col)?
+        }
+        Some(Shared::Array(data)) if anchor == (row, col) => {
+            formula::decompile(global, &data.rgce, &data.rgcb, row, col)?
+        }
+        Some(Shared::Table(data)) if anchor == (row, col) => {
+            let input1 = formula::cell(data.row_input1, data.col_input1, false, false);
+            let input2 = formula::cell(data.row_input2, data.col_input2, false, false);
+            match (data.two_inputs, data.row_input) {
+                (true, _) => format!("TABLE({},{})", input1, input2),
+                (false, true) => format!("TABLE({},)", input1),
+                (false, false) => format!("TABLE(,{})", input1),
+            }
+        }
+        // the other cells of an array or a table only keep their values
+        _ => return Ok(None),
+    };
+    Ok(Some(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorkbookReader;
+    use crate::{CellValue, Error, ErrorKind, FormulaError};
+    use std::io::{Cursor, Write};
+
+    fn record(r#type: u16, body: &[u8]) -> Vec<u8> {
+        let mut bytes = r#type.to_le_bytes().to_vec();
+        bytes.extend((body.len() as u16).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    fn bof(dt: u16) -> Vec<u8> {
+        let mut body = [0x0600, dt, 0, 0x07CC].map(u16::to_le_bytes).concat();
+        body.extend([0; 8]);
+        record(0x0809, &body)
+    }
+
+    // compound file with a workbook stream of one worksheet holding the records
+    fn workbook(records: &[Vec<u8>]) -> Cursor<Vec<u8>> {
+        let mut globals = bof(0x0005);
+        globals.extend(record(0x0042, &1200u16.to_le_bytes()));
+        let boundsheet_len = 4 + 14;
+        let eof = record(0x000A, &[]);
+        let position = (globals.len() + boundsheet_len + eof.len()) as u32;
+        let mut boundsheet = position.to_le_bytes().to_vec();
+        boundsheet.extend([0, 0, 6, 0]);
+        boundsheet.extend(b"Sheet1");
+        globals.extend(record(0x0085, &boundsheet));
+        globals.extend(&eof);
+        globals.extend(bof(0x0010));
+        globals.extend(records.concat());
+        globals.extend(&eof);
+
+        let mut compound_file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        let mut stream = compound_file.create_stream("/Workbook").unwrap();
+        stream.write_all(&globals).unwrap();
+        drop(stream);
+        let mut cursor = compound_file.into_inner();
+        cursor.set_position(0);
+        cursor
+    }
+
+    fn boolerr(col: u16, code: u8) -> Vec<u8> {
+        let mut body = [0, col, 15].map(u16::to_le_bytes).concat();
+        body.extend([code, 0x01]);
+        record(0x0205, &body)
+    }
+
+    #[test]
+    fn error_codes() {
+        let mut reader =
+            WorkbookReader::new(workbook(&[boolerr(0, 0x07), boolerr(1, 0x2B)])).unwrap();
+        let sheet = reader.sheet_by_index(0).unwrap();
+        assert_eq!(
+            sheet.cell(0, 0).unwrap().value,
+            CellValue::Error(ErrorKind::Div0)
+        );
+        assert_eq!(
+            sheet.cell(0, 1).unwrap().value,
+            CellValue::Error(ErrorKind::GettingData)
+        );
+
+        // unknown error codes keep their code, the sheet still loads
+        let mut reader =
+            WorkbookReader::new(workbook(&[boolerr(0, 0x2C), boolerr(1, 0x07)])).unwrap();
+        let sheet = reader.sheet_by_index(0).unwrap();
+        assert_eq!(
+            sheet.cell(0, 0).unwrap().value,
+            CellValue::Error(ErrorKind::Unknown(0x2C))
+        );
+        assert_eq!(
+            sheet.cell(0, 1).unwrap().value,
+            CellValue::Error(ErrorKind::Div0)
+        );
+    }
+
+    fn formula(row: u16, col: u16, rgce: &[u8]) -> Vec<u8> {
+        let mut body = [row, col, 15].map(u16::to_le_bytes).concat();
+        body.extend(0f64.to_le_bytes());
+        body.extend([0; 6]);
+        body.extend((rgce.len() as u16).to_le_bytes());
+        body.extend(rgce);
+        record(0x0006, &body)
+    }
+
+    // PtgExp or PtgTbl to the anchor of a shared formula
+    fn exp(ptg: u8, row: u16, col: u16) -> Vec<u8> {
+        [vec![ptg], [row, col].map(u16::to_le_bytes).concat()].concat()
+    }
+
+    // RefU of the cells sharing a formula
+    fn shared(first: (u16, u8), last: (u16, u8)) -> Vec<u8> {
+        let mut body = [first.0, last.0].map(u16::to_le_bytes).concat();
+        body.extend([first.1, last.1]);
+        body
+    }
+
+    #[test]
+    fn short_records() {
+        // OBJ and NOTE records shorter than their fixed fields are ignored, the stream stays in step
+        let obj = record(0x005D, &[0x15, 0x00, 0x12, 0x00]);
+        let note = record(0x001C, &[0; 6]);
+        let number = record(
+            0x0203,
+            &[
+                [0u16, 2, 15].map(u16::to_le_bytes).concat(),
+                1.5f64.to_le_bytes().to_vec(),
+            ]
+            .concat(),
+        );
+        let mut reader = WorkbookReader::new(workbook(&[obj, note, number])).unwrap();
+        let sheet = reader.sheet_by_index(0).unwrap();
+        assert_eq!(sheet.cell(0, 2).unwrap().value, CellValue::Number(1.5));
+    }
+
+    #[test]
+    fn shared_formulas() {
+        // B1+1 relative to the anchor A2, from the PtgRefN row offset -1 and column offset +1
+        let rgce = [0x2C, 0xFF, 0xFF, 0x01, 0xC0, 0x1E, 1, 0, 0x03];
+        let mut shrfmla = shared((1, 0), (2, 0));
+        shrfmla.extend([0, 2]);
+        shrfmla.extend((rgce.len() as u16).to_le_bytes());
+        shrfmla.extend(rgce);
+
+        let mut array = shared((4, 0), (5, 0));
+        array.extend([0; 6]);
+        array.extend([3, 0, 0x1E, 7, 0]);
+
+        let mut table = shared((7, 1), (8, 1));
+        table.extend([0x04, 0]);
+        table.extend([0u16, 0, 0, 0].map(u16::to_le_bytes).concat());
+
+        let mut reader = WorkbookReader::new(workbook(&[
+            // the SHRFMLA and ARRAY records follow the FORMULA record of their anchor
+            formula(1, 0, &exp(0x01, 1, 0)),
+            record(0x04BC, &shrfmla),
+            formula(2, 0, &exp(0x01, 1, 0)),
+            formula(4, 0, &exp(0x01, 4, 0)),
+            record(0x0221, &array),
+            formula(5, 0, &exp(0x01, 4, 0)),
+            formula(7, 1, &exp(0x02, 7, 1)),
+            record(0x0236, &table),
+            formula(8, 1, &exp(0x02, 7, 1)),
+        ]))
+        .unwrap();
+        let sheet = reader.sheet_by_index(0).unwrap();
+        let text = |row, col| match &sheet.cell(row, col).unwrap().value {
+            CellValue::Formula { text, .. } => Some(text.as_str()),
+            _ => None,
+        };
+        assert_eq!(text(1, 0), Some("B1+1"));
+        assert_eq!(text(2, 0), Some("B2+1"));
+        assert_eq!(text(4, 0), Some("7"));
+        // the other cells of an array or a table only keep their values
+        assert_eq!(text(5, 0), None);
+        assert_eq!(text(7, 1), Some("TABLE(A1,)"));
+        assert_eq!(text(8, 1), None);
+        assert!(sheet.formula_errors().is_empty());
+    }
+
+    #[test]
+    fn formula_errors() {
+        // an unknown token keeps the cached value and is reported
+        let mut reader = WorkbookReader::new(workbook(&[
+            formula(0, 0, &[0x00]),
+            formula(0, 1, &[0x1E, 1, 0]),
+        ]))
+        .unwrap();
+        let sheet = reader.sheet_by_index(0).unwrap();
+        assert_eq!(sheet.cell(0, 0).unwrap().value, CellValue::Number(0.0));
+        assert_eq!(
+            sheet.formula_errors(),
+            [FormulaError {
+                row: 0,
+                col: 0,
+                message: Error::Ptg(0x00).to_string(),
+            }]
+        );
+    }
+}
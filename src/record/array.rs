@@ -0,0 +1,20 @@
+use super::RefU;
+use binrw::BinRead;
+
+// 2.4.4
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub r#ref: RefU,
+    _flags: u16,
+    _unused: u32,
+
+    // ArrayParsedFormula
+    #[br(assert(_len as usize >= 14 + _cce as usize))]
+    _cce: u16,
+    #[br(count = _cce)]
+    pub rgce: Vec<u8>,
+    #[br(count = _len as usize - 14 - _cce as usize)]
+    pub rgcb: Vec<u8>,
+}
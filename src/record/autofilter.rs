@@ -0,0 +1,134 @@
+use super::rk::RkRec;
+use crate::workbook::ErrorKind;
+use binrw::BinRead;
+use encoding_rs::Encoding;
+use modular_bitfield::{
+    bitfield,
+    prelude::{B2, B9},
+};
+
+// 2.4.31
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    #[br(pad_size_to = _len)]
+    pub filter: Filter,
+}
+
+#[derive(Debug, BinRead)]
+pub struct Filter {
+    // zero-based column offset in the auto filter range
+    pub i_entry: u16,
+    pub info: Info,
+    pub doper1: DOper,
+    pub doper2: DOper,
+    #[br(if(doper1.vt == 0x06), args(doper1.cch()))]
+    str1: Option<XLUnicodeStringNoCch>,
+    #[br(if(doper2.vt == 0x06), args(doper2.cch()))]
+    str2: Option<XLUnicodeStringNoCch>,
+
+    #[br(ignore)]
+    pub value1: Option<String>,
+    #[br(ignore)]
+    pub value2: Option<String>,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        let filter = &mut self.filter;
+        filter.value1 = filter.doper1.decode(filter.str1.as_ref(), encoding);
+        filter.value2 = filter.doper2.decode(filter.str2.as_ref(), encoding);
+    }
+}
+
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Info {
+    // 0 for and, 1 for or
+    #[skip(setters)]
+    pub join: B2, // 2 bits
+    // whether the conditions are values selected in the list
+    #[skip(setters)]
+    pub simple1: bool, // 1 bit
+    #[skip(setters)]
+    pub simple2: bool, // 1 bit
+    #[skip(setters)]
+    pub top10: bool, // 1 bit
+    #[skip(setters)]
+    pub top: bool, // 1 bit
+    #[skip(setters)]
+    pub percent: bool, // 1 bit
+    #[skip(setters)]
+    pub top_n: B9, // 9 bits
+}
+
+// 2.5.9 AFDOper
+#[derive(Debug, BinRead)]
+pub struct DOper {
+    // 0 undefined, 2 RK, 4 number, 6 string, 8 boolean or error, 0x0C blanks, 0x0E non blanks
+    pub vt: u8,
+    // 1 <, 2 =, 3 <=, 4 >, 5 <>, 6 >=
+    pub grbit_sign: u8,
+    value: [u8; 8],
+}
+
+impl DOper {
+    pub(super) fn cch(&self) -> u8 {
+        self.value[4]
+    }
+
+    pub(super) fn decode(
+        &self,
+        string: Option<&XLUnicodeStringNoCch>,
+        encoding: &'static Encoding,
+    ) -> Option<String> {
+        let [b0, b1, b2, b3, ..] = self.value;
+        match self.vt {
+            0x02 => Some(
+                RkRec::from_bytes([0, 0, b0, b1, b2, b3])
+                    .value()
+                    .to_string(),
+            ),
+            0x04 => Some(f64::from_le_bytes(self.value).to_string()),
+            0x06 => string.map(|s| super::xlstring(encoding, s.hbyte, &s.bytes)),
+            // 2.5.10 Bes, bBoolErr then fError
+            0x08 if b1 == 0 => Some(if b0 == 0x01 { "TRUE" } else { "FALSE" }.to_string()),
+            0x08 => Some(ErrorKind::from_code(b0).to_string()),
+            0x0C | 0x0E => Some(String::new()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, BinRead)]
+#[br(import(cch: u8))]
+pub(super) struct XLUnicodeStringNoCch {
+    #[br(map = |x: u8| x == 0x00)]
+    hbyte: bool,
+    #[br(count = if hbyte { cch as usize } else { cch as usize * 2 })]
+    bytes: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DOper;
+    use encoding_rs::UTF_16LE;
+
+    fn bool_err(b_bool_err: u8, f_error: u8) -> Option<String> {
+        let doper = DOper {
+            vt: 0x08,
+            grbit_sign: 0x02,
+            value: [b_bool_err, f_error, 0, 0, 0, 0, 0, 0],
+        };
+        doper.decode(None, UTF_16LE)
+    }
+
+    #[test]
+    fn bool_err_operands() {
+        assert_eq!(bool_err(0x01, 0x00).as_deref(), Some("TRUE"));
+        assert_eq!(bool_err(0x00, 0x00).as_deref(), Some("FALSE"));
+        assert_eq!(bool_err(0x07, 0x01).as_deref(), Some("#DIV/0!"));
+    }
+}
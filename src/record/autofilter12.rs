@@ -0,0 +1,134 @@
+use super::{
+    autofilter::{DOper, XLUnicodeStringNoCch},
+    mergecells::Ref8,
+};
+use binrw::BinRead;
+use encoding_rs::Encoding;
+use std::io::Cursor;
+
+// 2.4.32
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 62))]
+    _len: u16,
+
+    // FrtRefHeader
+    #[br(assert(_rt == 0x087E))]
+    _rt: u16,
+    _grbit_frt: u16,
+    _ref8: Ref8,
+
+    // zero-based column offset in the auto filter range
+    pub i_entry: u16,
+    _hide_arrow: u32,
+    // 0 for criteria, else a filter by cell color, font color or icon
+    pub ft: u32,
+    _cft: u32,
+    c_criteria: u32,
+    _c_date_groupings: u32,
+    _flags: u32,
+    _unused: u32,
+    _id_list: u32,
+    _guid_sview: [u8; 16],
+    // color or icon of the filter
+    #[br(count = _len - 62)]
+    _rgb: Vec<u8>,
+
+    // the AF12Criteria go on in the following ContinueFrt12 records
+    #[br(parse_with = super::continues, args(0x087F))]
+    continues: Vec<Vec<u8>>,
+
+    #[br(ignore)]
+    pub criteria: Vec<(DOper, Option<String>)>,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        // after the FrtRefHeader of each ContinueFrt12 record
+        let bytes = self
+            .continues
+            .iter()
+            .flat_map(|c| c.get(12..).unwrap_or_default())
+            .copied()
+            .collect::<Vec<_>>();
+        let mut cursor = Cursor::new(bytes);
+        for _ in 0..self.c_criteria {
+            let Ok(criteria) = Criteria::read_le(&mut cursor) else {
+                break;
+            };
+            let value = criteria.doper.decode(criteria.str.as_ref(), encoding);
+            self.criteria.push((criteria.doper, value));
+        }
+    }
+}
+
+// 2.5.3 AF12Criteria
+#[derive(Debug, BinRead)]
+struct Criteria {
+    doper: DOper,
+    #[br(if(doper.vt == 0x06), args(doper.cch()))]
+    str: Option<XLUnicodeStringNoCch>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Data;
+    use binrw::BinRead;
+    use encoding_rs::UTF_16LE;
+    use std::io::Cursor;
+
+    fn record(r#type: u16, body: &[u8]) -> Vec<u8> {
+        let mut bytes = r#type.to_le_bytes().to_vec();
+        bytes.extend((body.len() as u16).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    // FrtRefHeader of the record type
+    fn header(rt: u16) -> Vec<u8> {
+        let mut bytes = [rt, 0].map(u16::to_le_bytes).concat();
+        bytes.extend([0; 8]);
+        bytes
+    }
+
+    // AF12Criteria of an equal string operand
+    fn criteria(s: &str) -> Vec<u8> {
+        let mut bytes = vec![0x06, 0x02, 0, 0, 0, 0, s.len() as u8, 1, 0, 0, 0x00];
+        bytes.extend(s.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn criteria_list() {
+        let mut body = header(0x087E);
+        body.extend(2u16.to_le_bytes());
+        // fHideArrow, ft, cft, cCriteria, cDateGroupings, flags, unused, idList
+        for field in [0u32, 0, 0, 3, 0, 0, 0, 0] {
+            body.extend(field.to_le_bytes());
+        }
+        body.extend([0; 16]);
+        let mut bytes = record(0x087E, &body)[2..].to_vec();
+        for s in ["Apple", "Pear", "Plum"] {
+            bytes.extend(record(0x087F, &[header(0x087F), criteria(s)].concat()));
+        }
+        let next = bytes.len() as u64;
+        bytes.extend(record(0x000A, &[]));
+
+        let mut cursor = Cursor::new(bytes);
+        let mut data = Data::read_le(&mut cursor).unwrap();
+        data.decode(UTF_16LE);
+
+        assert_eq!(data.i_entry, 2);
+        let values = data
+            .criteria
+            .iter()
+            .map(|(doper, value)| (doper.grbit_sign, value.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [(2, Some("Apple")), (2, Some("Pear")), (2, Some("Plum"))]
+        );
+        // the next record is left to the record reader
+        assert_eq!(cursor.position(), next);
+    }
+}
@@ -0,0 +1,11 @@
+use binrw::BinRead;
+
+// 2.4.32
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 2))]
+    _len: u16,
+
+    // number of columns of the auto filter range
+    pub c_entries: u16,
+}
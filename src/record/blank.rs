@@ -5,7 +5,7 @@ pub struct Data {
     #[br(assert(_len == 6))]
     _len: u16,
 
-    _row: u16,
-    _col: u16,
-    _ixfe: u16,
+    pub row: u16,
+    pub col: u16,
+    pub ixfe: u16,
 }
@@ -0,0 +1,115 @@
+use super::dxfn::Dxfn;
+use super::mergecells::Ref8;
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.42
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    #[br(pad_size_to = _len)]
+    pub rule: Rule,
+}
+
+#[derive(Debug, BinRead)]
+pub struct Rule {
+    // 1 cell value, 2 formula
+    pub ct: u8,
+    pub cp: u8,
+    _cce1: u16,
+    _cce2: u16,
+    pub dxf: Dxfn,
+    #[br(count = _cce1)]
+    pub rgce1: Vec<u8>,
+    #[br(count = _cce2)]
+    pub rgce2: Vec<u8>,
+}
+
+// 2.4.43
+#[derive(Debug, BinRead)]
+pub struct Data12 {
+    #[br(assert(_len >= 12))]
+    _len: u16,
+
+    // FrtRefHeader
+    #[br(assert(_rt == 0x087A))]
+    _rt: u16,
+    _grbit_frt: u16,
+    _ref8: Ref8,
+
+    #[br(pad_size_to = _len - 12)]
+    pub rule: Rule12,
+}
+
+#[derive(Debug, BinRead)]
+pub struct Rule12 {
+    // 1 cell value, 2 formula, 3 color scale, 4 data bar, 5 top ten, 6 icon set
+    pub ct: u8,
+    pub cp: u8,
+    _cce1: u16,
+    _cce2: u16,
+    // DXFN12
+    _cbdxf: u32,
+    #[br(if(_cbdxf != 0), pad_size_to = _cbdxf)]
+    pub dxf: Option<Dxfn>,
+    #[br(if(_cbdxf == 0))]
+    _reserved: Option<u16>,
+    #[br(count = _cce1)]
+    pub rgce1: Vec<u8>,
+    #[br(count = _cce2)]
+    pub rgce2: Vec<u8>,
+    // CFParsedFormula
+    _cce_active: u16,
+    #[br(count = _cce_active)]
+    _rgce_active: Vec<u8>,
+    #[br(map = |x: u8| x & 0x02 != 0)]
+    pub stop_if_true: bool,
+    pub priority: u16,
+}
+
+// 2.4.44
+#[derive(Debug, BinRead)]
+pub struct ExData {
+    #[br(assert(_len >= 18))]
+    _len: u16,
+
+    // FrtRefHeaderU
+    #[br(assert(_rt == 0x087B))]
+    _rt: u16,
+    _grbit_frt: u16,
+    _ref8: Ref8,
+
+    #[br(map = |x: u32| x != 0)]
+    pub is_cf12: bool,
+    // identifier of the CONDFMT record
+    pub id: u16,
+    #[br(if(!is_cf12), pad_size_to = _len - 18)]
+    pub content: Option<ExContent>,
+}
+
+// 2.5.35 CFExNonCF12
+#[derive(Debug, BinRead)]
+pub struct ExContent {
+    // index of the CF record in the CONDFMT
+    pub icf: u16,
+    _cp: u8,
+    _icf_template: u8,
+    pub priority: u16,
+    #[br(map = |x: u8| x & 0x02 != 0)]
+    pub stop_if_true: bool,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.rule.dxf.decode(encoding);
+    }
+}
+
+impl Data12 {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        if let Some(dxf) = self.rule.dxf.as_mut() {
+            dxf.decode(encoding);
+        }
+    }
+}
@@ -14,7 +14,7 @@ pub struct Data {
     #[br(assert(col_min <= col_max && col_max <= 0x0100))]
     pub col_max: u16,
     pub width: u16,
-    _ixfe: u16,
+    pub ixfe: u16,
 
     pub info: Info,
     _unused2: u16, // undefined ignored
@@ -35,12 +35,12 @@ pub struct Info {
     phonetic: bool,
     #[skip(setters)]
     reserved1: B4,
-    #[skip]
-    outlevel: B3,
+    #[skip(setters)]
+    pub outlevel: B3,
     #[skip]
     __: B1,
-    #[skip]
-    collapsed: bool,
+    #[skip(setters)]
+    pub collapsed: bool,
     #[skip(setters)]
     reserved2: B3,
 }
@@ -0,0 +1,39 @@
+use super::mergecells::Ref8;
+use binrw::BinRead;
+
+// 2.4.56
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub cf: CondFmtStructure,
+}
+
+// 2.4.57
+#[derive(Debug, BinRead)]
+pub struct Data12 {
+    _len: u16,
+
+    // FrtRefHeaderU
+    #[br(assert(_rt == 0x0879))]
+    _rt: u16,
+    _grbit_frt: u16,
+    _ref8: Ref8,
+
+    pub cf: CondFmtStructure,
+}
+
+// 2.5.29
+#[derive(Debug, BinRead)]
+pub struct CondFmtStructure {
+    // number of CF or CF12 records following
+    pub ccf: u16,
+    _flags: u16,
+    #[br(calc = _flags >> 1)]
+    pub id: u16,
+    pub ref_bound: Ref8,
+    // SqRefU
+    _cref: u16,
+    #[br(count = _cref)]
+    pub sqref: Vec<Ref8>,
+}
@@ -0,0 +1,12 @@
+use binrw::BinRead;
+
+// 2.4.78
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 4 && _len.is_multiple_of(2)))]
+    _len: u16,
+
+    _dbrtrw: u32,
+    #[br(count = (_len - 4) / 2)]
+    _rgdb: Vec<u16>,
+}
@@ -6,20 +6,21 @@ pub struct Data {
     #[br(assert(_len == 4))]
     _len: u16,
 
-    _info: Info,
-    #[br(assert(matches!(_height, 0..=8179)))]
-    _height: i16,
+    pub info: Info,
+    // height of the hidden rows when zero is set
+    #[br(assert(matches!(height, 0..=8179)))]
+    pub height: i16,
 }
 
 #[bitfield]
 #[derive(Debug, BinRead)]
 #[br(map = Self::from_bytes,
     assert(self.reserved() == 0))]
-struct Info {
+pub struct Info {
     #[skip]
     user_set: bool,
-    #[skip]
-    zero: bool,
+    #[skip(setters)]
+    pub zero: bool,
     #[skip]
     top_bdr: bool,
     #[skip]
@@ -0,0 +1,84 @@
+use super::{XLUnicodeString, mergecells::Ref8};
+use binrw::BinRead;
+use encoding_rs::Encoding;
+use modular_bitfield::{
+    bitfield,
+    prelude::{B3, B4, B8},
+};
+
+// 2.4.107
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub info: Info,
+
+    // prompt title, error title, prompt and error
+    xlstrs: [XLUnicodeString; 4],
+
+    // DVParsedFormula
+    _cce1: u16,
+    _unused1: u16,
+    #[br(count = _cce1)]
+    pub rgce1: Vec<u8>,
+    _cce2: u16,
+    _unused2: u16,
+    #[br(count = _cce2)]
+    pub rgce2: Vec<u8>,
+
+    // SqRef
+    _cref: u16,
+    #[br(count = _cref)]
+    pub sqref: Vec<Ref8>,
+
+    #[br(ignore)]
+    pub prompt_title: String,
+    #[br(ignore)]
+    pub error_title: String,
+    #[br(ignore)]
+    pub prompt: String,
+    #[br(ignore)]
+    pub error: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        // empty strings are stored as a single null character
+        let decode = |s: &XLUnicodeString| {
+            super::xlstring(encoding, s.hbyte, &s.bytes)
+                .trim_end_matches('\0')
+                .to_string()
+        };
+        let [prompt_title, error_title, prompt, error] = &self.xlstrs;
+        self.prompt_title = decode(prompt_title);
+        self.error_title = decode(error_title);
+        self.prompt = decode(prompt);
+        self.error = decode(error);
+    }
+}
+
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Info {
+    #[skip(setters)]
+    pub val_type: B4, // 4 bits
+    #[skip(setters)]
+    pub err_style: B3, // 3 bits
+    #[skip(setters)]
+    pub str_lookup: bool, // 1 bit
+    #[skip(setters)]
+    pub allow_blank: bool, // 1 bit
+    #[skip(setters)]
+    pub suppress_combo: bool, // 1 bit
+    #[skip]
+    __: B8, // 8 bits, IME mode
+    #[skip(setters)]
+    pub show_input_msg: bool, // 1 bit
+    #[skip(setters)]
+    pub show_error_msg: bool, // 1 bit
+    #[skip(setters)]
+    pub typ_operator: B4, // 4 bits
+    #[skip]
+    __: B8, // 8 bits
+}
@@ -0,0 +1,14 @@
+use binrw::BinRead;
+
+// 2.4.82
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _flags: u16,
+    _x_left: u32,
+    _y_top: u32,
+    _id_obj: u32,
+    // number of DV records following
+    pub idv_mac: u32,
+}
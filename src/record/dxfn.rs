@@ -0,0 +1,148 @@
+use super::{
+    XLUnicodeString,
+    xf::{Borders, Fill},
+};
+use binrw::BinRead;
+use encoding_rs::Encoding;
+use modular_bitfield::{
+    bitfield,
+    prelude::{B2, B6, B8, B15},
+};
+
+// 2.5.91, formatting of conditional formatting rules
+#[derive(Debug, BinRead)]
+pub struct Dxfn {
+    pub flags: Flags,
+
+    #[br(if(flags.atr_num() && flags.ifmt_user()))]
+    num_usr: Option<NumUsr>,
+    #[br(if(flags.atr_num() && !flags.ifmt_user()))]
+    num_ifmt: Option<[u8; 2]>,
+    #[br(if(flags.atr_fnt()))]
+    pub font: Option<Font>,
+    #[br(if(flags.atr_alc()))]
+    _alc: Option<[u8; 8]>,
+    // DXFBdr, the layout of the XF borders
+    #[br(if(flags.atr_bdr()))]
+    pub borders: Option<Borders>,
+    // DXFPat, 10 unused bits followed by the layout of the XF fill
+    #[br(if(flags.atr_pat()))]
+    _pat_unused: Option<u8>,
+    #[br(if(flags.atr_pat()))]
+    pub fill: Option<Fill>,
+    #[br(if(flags.atr_prot()))]
+    _prot: Option<u16>,
+
+    #[br(ignore)]
+    pub num_fmt: Option<NumFmt>,
+}
+
+impl Dxfn {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.num_fmt = match (&self.num_usr, &self.num_ifmt) {
+            (Some(usr), _) => Some(NumFmt::Code(super::xlstring(
+                encoding,
+                usr.fmt.hbyte,
+                &usr.fmt.bytes,
+            ))),
+            (_, Some([_, ifmt])) => Some(NumFmt::Index(*ifmt)),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Debug)]
+pub enum NumFmt {
+    Index(u8),
+    Code(String),
+}
+
+// the Ninch flags are set for the attributes left unchanged
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Flags {
+    #[skip]
+    __: B8, // alignment
+    #[skip]
+    __: B2, // protection
+    #[skip(setters)]
+    pub left_ninch: bool,
+    #[skip(setters)]
+    pub right_ninch: bool,
+    #[skip(setters)]
+    pub top_ninch: bool,
+    #[skip(setters)]
+    pub bottom_ninch: bool,
+    #[skip]
+    __: B2, // diagonals
+    #[skip(setters)]
+    pub fls_ninch: bool,
+    #[skip(setters)]
+    pub icv_f_ninch: bool,
+    #[skip(setters)]
+    pub icv_b_ninch: bool,
+    #[skip]
+    __: B6, // number format, font, reserved
+    #[skip(setters)]
+    pub atr_num: bool,
+    #[skip(setters)]
+    pub atr_fnt: bool,
+    #[skip(setters)]
+    pub atr_alc: bool,
+    #[skip(setters)]
+    pub atr_bdr: bool,
+    #[skip(setters)]
+    pub atr_pat: bool,
+    #[skip(setters)]
+    pub atr_prot: bool,
+    #[skip]
+    __: bool,
+    #[skip(setters)]
+    pub ifmt_user: bool,
+    #[skip]
+    __: B15,
+}
+
+// 2.5.95 DXFNumUsr
+#[derive(Debug, BinRead)]
+struct NumUsr {
+    _cb: u16,
+    fmt: XLUnicodeString,
+}
+
+// 2.5.96 DXFFntD
+#[derive(Debug, BinRead)]
+pub struct Font {
+    _cch_font: u8,
+    _st_font_name: [u8; 63],
+    // Stxp, -1 when unchanged
+    pub height: i32,
+    _ts: u32,
+    #[br(calc = _ts & 0x02 != 0)]
+    pub italic: bool,
+    #[br(calc = _ts & 0x80 != 0)]
+    pub strike_out: bool,
+    pub bls: u16,
+    _sss: u16,
+    pub uls: u8,
+    _charset: u8,
+    _unused1: u16,
+    // -1 when unchanged
+    pub icv: i32,
+    _reserved: u32,
+    _ts_ninch: u32,
+    #[br(calc = _ts_ninch & 0x02 != 0)]
+    pub italic_ninch: bool,
+    #[br(calc = _ts_ninch & 0x80 != 0)]
+    pub strike_out_ninch: bool,
+    _sss_ninch: u32,
+    #[br(map = |x: u32| x == 1)]
+    pub uls_ninch: bool,
+    #[br(map = |x: u32| x == 1)]
+    pub bls_ninch: bool,
+    _unused2: u32,
+    _ich: i32,
+    _cch: i32,
+    _ifnt: u16,
+}
@@ -0,0 +1,26 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.98
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _flags: u16,
+    _reserved: u32, // ixals & reserved, or unused for add-in and DDE/OLE links
+
+    #[br(assert(_len as usize >= 8 + xlstr.bytes.len()))]
+    xlstr: super::ShortXLUnicodeString,
+
+    #[br(count = _len as usize - 8 - xlstr.bytes.len())]
+    _body: Vec<u8>, // nameDefinition, ignored
+
+    #[br(ignore)]
+    pub name: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.name = super::xlstring(encoding, self.xlstr.hbyte, &self.xlstr.bytes);
+    }
+}
@@ -0,0 +1,19 @@
+use binrw::BinRead;
+
+// 2.4.106
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _cxti: u16,
+    #[br(count = _cxti)]
+    pub xtis: Vec<Xti>,
+}
+
+// 2.5.311
+#[derive(Debug, BinRead)]
+pub struct Xti {
+    pub isupbook: u16,
+    pub itab_first: i16, // -1: deleted sheet, -2: workbook-level
+    pub itab_last: i16,
+}
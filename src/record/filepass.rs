@@ -1,11 +1,86 @@
+use crate::error::{Error, Result};
 use binrw::BinRead;
+use std::io::Cursor;
 
 // 2.4.117
-#[allow(dead_code)]
 #[derive(Debug, BinRead)]
 pub struct Data {
+    #[br(assert(_len >= 2))]
     _len: u16,
-    _enc_type: u16,
+
+    pub enc_type: u16,
     #[br(count = _len - 2)]
-    _enc_info: Vec<u8>,
+    enc_info: Vec<u8>,
+}
+
+impl Data {
+    pub fn decode(&self) -> Result<Encryption> {
+        let mut stream = Cursor::new(&self.enc_info);
+        let encryption = match self.enc_type {
+            0x0000 => Encryption::Xor(XorInfo::read_le(&mut stream)?),
+            0x0001 => {
+                let (major, minor) = <(u16, u16)>::read_le(&mut stream)?;
+                match (major, minor) {
+                    (1, 1) => Encryption::Rc4(Rc4Info::read_le(&mut stream)?),
+                    (2..=4, 2) => Encryption::CryptoApi(CryptoApiInfo::read_le(&mut stream)?),
+                    _ => return Err(Error::Encryption(major, minor)),
+                }
+            }
+            _ => return Err(Error::Encryption(self.enc_type, 0)),
+        };
+        Ok(encryption)
+    }
+}
+
+#[derive(Debug)]
+pub enum Encryption {
+    Xor(XorInfo),
+    Rc4(Rc4Info),
+    CryptoApi(CryptoApiInfo),
+}
+
+// 2.5.343
+#[derive(Debug, BinRead)]
+#[br(little)]
+pub struct XorInfo {
+    pub key: u16,
+    pub verifier: u16,
+}
+
+// [MS-OFFCRYPTO] 2.3.6.1
+#[derive(Debug, BinRead)]
+#[br(little)]
+pub struct Rc4Info {
+    pub salt: [u8; 16],
+    pub verifier: [u8; 16],
+    pub verifier_hash: [u8; 16],
+}
+
+// [MS-OFFCRYPTO] 2.3.5.1
+#[derive(Debug, BinRead)]
+#[br(little)]
+pub struct CryptoApiInfo {
+    _flags: u32,
+    _header_size: u32,
+
+    // EncryptionHeader
+    _header_flags: u32,
+    _size_extra: u32,
+    pub alg_id: u32,
+    pub alg_id_hash: u32,
+    pub key_size: u32, // bits, 0 means 40
+    _provider_type: u32,
+    _reserved1: u32,
+    _reserved2: u32,
+    #[br(count = _header_size.saturating_sub(32))]
+    _csp_name: Vec<u8>,
+
+    // EncryptionVerifier
+    #[br(assert(_salt_size == 16))]
+    _salt_size: u32,
+    pub salt: [u8; 16],
+    pub verifier: [u8; 16],
+    #[br(assert(_verifier_hash_size == 20))]
+    _verifier_hash_size: u32,
+    pub verifier_hash: [u8; 20],
 }
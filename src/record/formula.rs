@@ -0,0 +1,49 @@
+use binrw::BinRead;
+
+// 2.4.127
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 22))]
+    _len: u16,
+
+    pub row: u16,
+    pub col: u16,
+    pub ixfe: u16,
+
+    #[br(map = |x: [u8; 8]| FormulaValue::from(x))]
+    pub value: FormulaValue,
+    _flags: u16,
+    _chn: u32, // ignore
+
+    // CellParsedFormula
+    #[br(assert(_len as usize >= 22 + _cce as usize))]
+    _cce: u16,
+    #[br(count = _cce)]
+    pub rgce: Vec<u8>,
+    #[br(count = _len as usize - 22 - _cce as usize)]
+    pub rgcb: Vec<u8>,
+}
+
+// 2.5.133
+#[derive(Debug)]
+pub enum FormulaValue {
+    Number(f64),
+    String, // the value is in the following String record
+    Bool(bool),
+    Error(u8),
+    Empty,
+}
+
+impl From<[u8; 8]> for FormulaValue {
+    fn from(bytes: [u8; 8]) -> Self {
+        if bytes[6..] != [0xFF, 0xFF] {
+            return Self::Number(f64::from_le_bytes(bytes));
+        }
+        match bytes[0] {
+            0x00 => Self::String,
+            0x01 => Self::Bool(bytes[2] == 0x01),
+            0x02 => Self::Error(bytes[2]),
+            _ => Self::Empty,
+        }
+    }
+}
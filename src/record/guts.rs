@@ -0,0 +1,14 @@
+use binrw::BinRead;
+
+// 2.4.134
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 8))]
+    _len: u16,
+
+    _dx_rw_gut: u16,
+    _dy_col_gut: u16,
+    // number of row outline levels, including the ungrouped level, 0 without groups
+    pub i_level_rw_mac: u16,
+    pub i_level_col_mac: u16,
+}
@@ -0,0 +1,23 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.136 Header, 2.4.124 Footer
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    // empty when the sheet has no header or footer
+    #[br(if(_len > 0))]
+    xlstr: Option<super::XLUnicodeString>,
+
+    #[br(ignore)]
+    pub text: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        if let Some(xlstr) = &self.xlstr {
+            self.text = super::xlstring(encoding, xlstr.hbyte, &xlstr.bytes);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::mergecells::Ref8;
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.140
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 24))]
+    _len: u16,
+
+    pub ref8: Ref8,
+    _hlink_clsid: [u8; 16],
+
+    #[br(pad_size_to = _len - 24)]
+    hyperlink: Hyperlink,
+
+    #[br(ignore)]
+    pub target: Option<String>,
+    #[br(ignore)]
+    pub location: Option<String>,
+    #[br(ignore)]
+    pub display: Option<String>,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        let hyperlink = &self.hyperlink;
+        self.display = hyperlink.display_name.as_ref().map(HyperlinkString::decode);
+        self.location = hyperlink.location.as_ref().map(HyperlinkString::decode);
+        self.target = match (&hyperlink.moniker, &hyperlink.ole_moniker) {
+            (Some(Moniker::Url { url, .. }), _) => Some(utf16z(url)),
+            (Some(Moniker::File(file)), _) => Some(file.decode(encoding)),
+            (_, Some(ole_moniker)) => Some(ole_moniker.decode()),
+            _ => None,
+        };
+    }
+}
+
+// [MS-OSHARED] 2.3.7.1
+#[derive(Debug, BinRead)]
+struct Hyperlink {
+    #[br(assert(_stream_version == 2))]
+    _stream_version: u32,
+    _flags: u32,
+
+    #[br(if(_flags & 0x0010 != 0))] // hlstmfHasDisplayName
+    display_name: Option<HyperlinkString>,
+    #[br(if(_flags & 0x0080 != 0))] // hlstmfHasFrameName
+    _target_frame_name: Option<HyperlinkString>,
+    #[br(if(_flags & 0x0101 == 0x0001))] // hlstmfHasMoniker
+    moniker: Option<Moniker>,
+    #[br(if(_flags & 0x0101 == 0x0101))] // hlstmfMonikerSavedAsStr
+    ole_moniker: Option<HyperlinkString>,
+    // unknown monikers have no length, the rest of the record can't be located
+    #[br(if(_flags & 0x0008 != 0 && !matches!(moniker, Some(Moniker::Other))))]
+    location: Option<HyperlinkString>,
+}
+
+// [MS-OSHARED] 2.3.7.9
+#[derive(Debug, BinRead)]
+struct HyperlinkString {
+    _length: u32,
+    #[br(count = _length)]
+    chars: Vec<u16>,
+}
+
+impl HyperlinkString {
+    fn decode(&self) -> String {
+        String::from_utf16_lossy(&self.chars)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+}
+
+// [MS-OSHARED] 2.3.7.2
+#[derive(Debug, BinRead)]
+enum Moniker {
+    // [MS-OSHARED] 2.3.7.6, CLSID_URLMoniker
+    #[br(magic = b"\xE0\xC9\xEA\x79\xF9\xBA\xCE\x11\x8C\x82\x00\xAA\x00\x4B\xA9\x0B")]
+    Url {
+        _length: u32,
+        #[br(count = _length / 2)]
+        url: Vec<u16>,
+    },
+    // [MS-OSHARED] 2.3.7.8, CLSID_FileMoniker
+    #[br(magic = b"\x03\x03\x00\x00\x00\x00\x00\x00\xC0\x00\x00\x00\x00\x00\x00\x46")]
+    File(FileMoniker),
+    // composite, item and other monikers
+    Other,
+}
+
+#[derive(Debug, BinRead)]
+struct FileMoniker {
+    c_anti: u16,
+    _ansi_length: u32,
+    #[br(count = _ansi_length)]
+    ansi_path: Vec<u8>,
+    _end_server: u16,
+    _version_number: u16,
+    _reserved: [u8; 20],
+    _cb_unicode_path_size: u32,
+    #[br(if(_cb_unicode_path_size > 0))]
+    _cb_unicode_path_bytes: u32,
+    #[br(if(_cb_unicode_path_size > 0))]
+    _us_key_value: u16,
+    #[br(count = _cb_unicode_path_bytes / 2)]
+    unicode_path: Vec<u16>,
+}
+
+impl FileMoniker {
+    fn decode(&self, encoding: &'static Encoding) -> String {
+        let path = if self.unicode_path.is_empty() {
+            let len = self.ansi_path.iter().position(|&b| b == 0);
+            let bytes = &self.ansi_path[..len.unwrap_or(self.ansi_path.len())];
+            super::xlstring(encoding, true, bytes)
+        } else {
+            String::from_utf16_lossy(&self.unicode_path)
+        };
+        // each anti moniker goes up one directory
+        "..\\".repeat(self.c_anti as usize) + &path
+    }
+}
+
+fn utf16z(chars: &[u16]) -> String {
+    let len = chars.iter().position(|&c| c == 0).unwrap_or(chars.len());
+    String::from_utf16_lossy(&chars[..len])
+}
+
+// 2.4.141
+#[derive(Debug, BinRead)]
+pub struct TooltipData {
+    #[br(assert(_len >= 10 && _len.is_multiple_of(2)))]
+    _len: u16,
+
+    // FrtRefHeaderNoGrbit
+    #[br(assert(_rt == 0x0800))]
+    _rt: u16,
+    pub ref8: Ref8,
+
+    #[br(count = (_len - 10) / 2)]
+    chars: Vec<u16>,
+
+    #[br(ignore)]
+    pub tooltip: String,
+}
+
+impl TooltipData {
+    pub fn decode(&mut self) {
+        self.tooltip = utf16z(&self.chars);
+    }
+}
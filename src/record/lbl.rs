@@ -0,0 +1,63 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.150
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _flags: u16,
+    #[br(calc = _flags & 0x0001 == 0x0001)]
+    pub hidden: bool,
+    #[br(calc = _flags & 0x0020 == 0x0020)]
+    pub builtin: bool,
+
+    _chkey: u8,
+    _cch: u8,
+    _cce: u16,
+    _reserved3: u16,
+    pub itab: u16, // 0: workbook scope, otherwise 1-based sheet index
+    _reserved4: u32,
+
+    // XLUnicodeStringNoCch
+    #[br(map = |x: u8| x == 0x00)]
+    hbyte: bool,
+    #[br(count = if hbyte { _cch as u16 } else { _cch as u16 * 2 })]
+    bytes: Vec<u8>,
+
+    // NameParsedFormula
+    #[br(count = _cce, assert(_len as usize >= 15 + bytes.len() + rgce.len()))]
+    pub rgce: Vec<u8>,
+    #[br(count = _len as usize - 15 - bytes.len() - _cce as usize)]
+    pub rgcb: Vec<u8>,
+
+    #[br(ignore)]
+    pub name: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.name = if self.builtin && self.bytes.len() <= 2 {
+            let name = match self.bytes.first() {
+                Some(0x00) => "Consolidate_Area",
+                Some(0x01) => "Auto_Open",
+                Some(0x02) => "Auto_Close",
+                Some(0x03) => "Extract",
+                Some(0x04) => "Database",
+                Some(0x05) => "Criteria",
+                Some(0x06) => "Print_Area",
+                Some(0x07) => "Print_Titles",
+                Some(0x08) => "Recorder",
+                Some(0x09) => "Data_Form",
+                Some(0x0A) => "Auto_Activate",
+                Some(0x0B) => "Auto_Deactivate",
+                Some(0x0C) => "Sheet_Title",
+                Some(0x0D) => "_FilterDatabase",
+                _ => "",
+            };
+            format!("_xlnm.{name}")
+        } else {
+            super::xlstring(encoding, self.hbyte, &self.bytes)
+        };
+    }
+}
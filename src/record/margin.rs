@@ -0,0 +1,10 @@
+use binrw::BinRead;
+
+// 2.4.151 LeftMargin, 2.4.219 RightMargin, 2.4.328 TopMargin, 2.4.26 BottomMargin
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 8))]
+    _len: u16,
+
+    pub num: f64, // inches
+}
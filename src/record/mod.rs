@@ -2,42 +2,114 @@
 #![allow(unused_parens)]
 #![allow(dead_code)]
 
+pub mod array;
+pub mod autofilter;
+pub mod autofilter12;
+mod autofilterinfo;
 mod blank;
 pub mod bof;
-mod boolerr;
+pub mod boolerr;
 pub mod boundsheet8;
+pub mod cf;
 mod codepage;
-mod colinfo;
+pub mod colinfo;
+pub mod condfmt;
 mod date1904;
+mod dbcell;
 mod defaultrowheight;
 mod defautlcolwidth;
 mod dimensions;
-mod filepass;
+pub mod dv;
+mod dval;
+pub mod dxfn;
+pub mod externname;
+pub mod externsheet;
+pub mod filepass;
 pub mod font;
 pub mod format;
+pub mod formula;
+mod guts;
+mod headerfooter;
+mod hlink;
 mod label;
 mod labelsst;
-mod mergecells;
+pub mod lbl;
+mod margin;
+pub mod mergecells;
 mod mulblank;
 mod mulrk;
+mod note;
 mod number;
+mod obj;
+mod pagebreaks;
 mod palette;
+mod pane;
+mod printflag;
 mod rk;
-mod rowinfo;
-mod sst;
+pub mod rowinfo;
+mod scl;
+mod selection;
+mod setup;
+pub mod shrfmla;
+pub mod sst;
+mod string;
 pub mod style;
-mod styleext;
+pub mod styleext;
+pub mod supbook;
+pub mod table;
+mod theme;
+mod txo;
+mod window1;
+mod window2;
+mod wsbool;
 pub mod xf;
-mod xfext;
+pub mod xfext;
 
-use binrw::{BinRead, helpers::until_eof};
+use crate::error::Result;
+use binrw::{BinRead, BinResult, io::SeekFrom};
 use encoding_rs::Encoding;
 use enum_display::EnumDisplay;
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    io::{Read, Seek},
+};
 
-#[derive(Debug, BinRead)]
-#[br(little)]
-pub struct Records(#[br(parse_with = until_eof)] pub Vec<Record>);
+/// Pull-based reader yielding one record at a time, until the EOF record or the end of the stream.
+pub struct RecordReader<S> {
+    stream: S,
+    eof: bool,
+}
+
+impl<S: Read + Seek> RecordReader<S> {
+    pub fn new(stream: S) -> Self {
+        RecordReader { stream, eof: false }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Read + Seek> Iterator for RecordReader<S> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.eof {
+            return None;
+        }
+
+        match Record::read_le(&mut self.stream) {
+            Ok(record) => {
+                self.eof = matches!(record, Record::Eof(_));
+                Some(Ok(record))
+            }
+            Err(error) => {
+                self.eof = true;
+                (!error.is_eof()).then(|| Err(error.into()))
+            }
+        }
+    }
+}
 
 #[derive(Debug, BinRead, EnumDisplay)]
 pub enum Record {
@@ -56,47 +128,141 @@ pub enum Record {
     Font(font::Data),
     #[br(magic(0x041Eu16))]
     Format(format::Data),
+    #[br(magic(0x0023u16))]
+    ExternName(externname::Data),
+    #[br(magic(0x0017u16))]
+    ExternSheet(externsheet::Data),
+    #[br(magic(0x0018u16))]
+    Lbl(lbl::Data),
     #[br(magic(0x0092u16))]
     Palette(palette::Data),
     #[br(magic(0x00FCu16))]
     Sst(sst::Data),
     #[br(magic(0x0293u16))]
     Style(style::Data),
+    #[br(magic(0x01AEu16))]
+    SupBook(supbook::Data),
+    #[br(magic(0x003Du16))]
+    Window1(window1::Data),
     #[br(magic(0x00E0u16))]
     XF(xf::Data),
     #[br(magic(0x087Du16))]
     XFExt(xfext::Data),
+    #[br(magic(0x0896u16))]
+    Theme(theme::Data),
     #[br(magic(0x000Au16))]
     Eof(Empty),
     // sheet
+    #[br(magic(0x0221u16))]
+    Array(array::Data),
+    #[br(magic(0x009Eu16))]
+    AutoFilter(autofilter::Data),
+    #[br(magic(0x087Eu16))]
+    AutoFilter12(autofilter12::Data),
+    #[br(magic(0x009Du16))]
+    AutoFilterInfo(autofilterinfo::Data),
     #[br(magic(0x0201u16))]
     Blank(blank::Data),
     #[br(magic(0x0205u16))]
     BoolErr(boolerr::Data),
+    #[br(magic(0x01B1u16))]
+    CF(cf::Data),
+    #[br(magic(0x087Au16))]
+    CF12(cf::Data12),
+    #[br(magic(0x087Bu16))]
+    CFEx(cf::ExData),
     #[br(magic(0x007Du16))]
     ColInfo(colinfo::Data),
+    #[br(magic(0x01B0u16))]
+    CondFmt(condfmt::Data),
+    #[br(magic(0x0879u16))]
+    CondFmt12(condfmt::Data12),
     #[br(magic(0x0055u16))]
     DefaultColWidth(defautlcolwidth::Data),
+    #[br(magic(0x00D7u16))]
+    DbCell(dbcell::Data),
     #[br(magic(0x0225u16))]
     DefaultRowHeight(defaultrowheight::Data),
     #[br(magic(0x0200u16))]
     Dimensions(dimensions::Data),
+    #[br(magic(0x01BEu16))]
+    Dv(dv::Data),
+    #[br(magic(0x01B2u16))]
+    DVal(dval::Data),
+    #[br(magic(0x0029u16))]
+    BottomMargin(margin::Data),
+    #[br(magic(0x009Bu16))]
+    FilterMode(Empty),
+    #[br(magic(0x0006u16))]
+    Formula(formula::Data),
+    #[br(magic(0x0080u16))]
+    Guts(guts::Data),
+    #[br(magic(0x0083u16))]
+    HCenter(printflag::Data),
+    #[br(magic(0x0014u16))]
+    Header(headerfooter::Data),
+    #[br(magic(0x001Bu16))]
+    HorizontalPageBreaks(pagebreaks::Data),
+    #[br(magic(0x01B8u16))]
+    HLink(hlink::Data),
+    #[br(magic(0x0800u16))]
+    HLinkTooltip(hlink::TooltipData),
+    #[br(magic(0x0015u16))]
+    Footer(headerfooter::Data),
     #[br(magic(0x0204u16))]
     Label(label::Data),
     #[br(magic(0x00FDu16))]
     LabelSST(labelsst::Data),
+    #[br(magic(0x0026u16))]
+    LeftMargin(margin::Data),
     #[br(magic(0x00E5u16))]
     MergeCells(mergecells::Data),
     #[br(magic(0x00BEu16))]
     MulBlank(mulblank::Data),
     #[br(magic(0x00BDu16))]
     MulRk(mulrk::Data),
+    #[br(magic(0x001Cu16))]
+    Note(note::Data),
     #[br(magic(0x0203u16))]
     Number(number::Data),
+    #[br(magic(0x005Du16))]
+    Obj(obj::Data),
+    #[br(magic(0x0041u16))]
+    Pane(pane::Data),
+    #[br(magic(0x002Bu16))]
+    PrintGrid(printflag::Data),
+    #[br(magic(0x002Au16))]
+    PrintRowCol(printflag::Data),
+    #[br(magic(0x0027u16))]
+    RightMargin(margin::Data),
     #[br(magic(0x027Eu16))]
     Rk(rk::Data),
     #[br(magic(0x0208u16))]
     RowInfo(rowinfo::Data),
+    #[br(magic(0x00A0u16))]
+    Scl(scl::Data),
+    #[br(magic(0x001Du16))]
+    Selection(selection::Data),
+    #[br(magic(0x00A1u16))]
+    Setup(setup::Data),
+    #[br(magic(0x04BCu16))]
+    ShrFmla(shrfmla::Data),
+    #[br(magic(0x0207u16))]
+    String(string::Data),
+    #[br(magic(0x0236u16))]
+    Table(table::Data),
+    #[br(magic(0x0028u16))]
+    TopMargin(margin::Data),
+    #[br(magic(0x01B6u16))]
+    TxO(txo::Data),
+    #[br(magic(0x0084u16))]
+    VCenter(printflag::Data),
+    #[br(magic(0x001Au16))]
+    VerticalPageBreaks(pagebreaks::Data),
+    #[br(magic(0x023Eu16))]
+    Window2(window2::Data),
+    #[br(magic(0x0081u16))]
+    WsBool(wsbool::Data),
 
     Ignore(Data),
 }
@@ -110,6 +276,22 @@ pub struct Data {
     pub bytes: Vec<u8>,
 }
 
+// bodies of the following records of the given type, the next record is left to the record reader
+#[binrw::parser(reader, endian)]
+pub fn continues(r#type: u16) -> BinResult<Vec<Vec<u8>>> {
+    let mut continues = Vec::new();
+    loop {
+        let pos = reader.stream_position()?;
+        match Data::read_options(reader, endian, ()) {
+            Ok(data) if data.r#type == r#type => continues.push(data.bytes),
+            _ => {
+                reader.seek(SeekFrom::Start(pos))?;
+                return Ok(continues);
+            }
+        }
+    }
+}
+
 /* #[cfg(debug_assertions)]
 #[derive(Debug, BinRead)]
 pub struct Common {
@@ -125,28 +307,39 @@ pub struct Empty {
 }
 
 #[derive(Debug, BinRead)]
+#[br(little)]
 pub struct XLUnicodeString {
     _cch: u16,
 
     #[br(assert(_reserved == 0x00 || _reserved == 0x01))]
     _reserved: u8,
     #[br(calc = _reserved == 0x00)]
-    hbyte: bool,
+    pub hbyte: bool,
 
-    #[br(count = if hbyte { _cch } else { _cch * 2 })]
-    bytes: Vec<u8>,
+    #[br(count = if hbyte { _cch as usize } else { _cch as usize * 2 })]
+    pub bytes: Vec<u8>,
 }
 
 #[derive(Debug, BinRead)]
-struct ShortXLUnicodeString {
+#[br(little)]
+pub struct ShortXLUnicodeString {
     _cch: u8,
     #[br(map = |x: u8| x == 0x00)]
-    hbyte: bool,
-    #[br(count = if hbyte { _cch } else { _cch * 2 })]
-    bytes: Vec<u8>,
+    pub hbyte: bool,
+    #[br(count = if hbyte { _cch as usize } else { _cch as usize * 2 })]
+    pub bytes: Vec<u8>,
+}
+
+// 2.5.207
+#[derive(Debug, BinRead, Clone, Copy)]
+pub struct RefU {
+    pub row_first: u16,
+    pub row_last: u16,
+    pub col_first: u8,
+    pub col_last: u8,
 }
 
-fn xlstring(encoding: &'static Encoding, hbyte: bool, bytes: &[u8]) -> String {
+pub fn xlstring(encoding: &'static Encoding, hbyte: bool, bytes: &[u8]) -> String {
     let bytes = if hbyte {
         let bytes = bytes.iter().flat_map(|b| [*b, 0x00]).collect::<Vec<_>>();
         Cow::Owned(bytes)
@@ -159,7 +352,6 @@ fn xlstring(encoding: &'static Encoding, hbyte: bool, bytes: &[u8]) -> String {
 #[cfg(feature = "tracing")]
 use std::{collections::HashMap, sync::LazyLock};
 #[cfg(feature = "tracing")]
-
 pub static RECORDS: LazyLock<HashMap<u16, &'static str>> = LazyLock::new(|| {
     HashMap::from([
         (0x0006, "Formula"),
@@ -5,13 +5,13 @@ use binrw::BinRead;
 pub struct Data {
     _len: u16,
 
-    _row: u16,
-    #[br(assert(_col_min <= 254))]
-    _col_min: u16,
+    pub row: u16,
+    #[br(assert(col_min <= 254))]
+    pub col_min: u16,
 
     #[br(count = (_len - 6) / 2)]
-    _ixfs: Vec<u16>,
+    pub ixfs: Vec<u16>,
 
-    #[br(assert(_col_min < _col_max))]
+    #[br(assert(col_min < _col_max))]
     _col_max: u16,
 }
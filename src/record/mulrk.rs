@@ -16,9 +16,6 @@ pub struct Data {
     #[br(assert(col_min < _col_max))]
     _col_max: u16,
 
-    #[br(calc = _rks.iter().map(|rk| {
-        let v = rk.num() as f64;
-        (rk.ixfe(), if rk.fx100() { v / 100.0 } else { v })
-    }).collect::<Vec<_>>())]
+    #[br(calc = _rks.iter().map(|rk| (rk.ixfe(), rk.value())).collect::<Vec<_>>())]
     pub values: Vec<(u16, f64)>, // (ixfe, num)
 }
@@ -0,0 +1,31 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.179 NoteSh
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub row: u16,
+    pub col: u16,
+
+    _flags: u16,
+    #[br(calc = _flags & 0x0002 == 0x0002)]
+    pub show: bool,
+
+    pub id_obj: u16,
+
+    #[br(assert(_len as usize >= 11 + xlstr.bytes.len()))]
+    xlstr: super::XLUnicodeString,
+    #[br(count = _len as usize - 11 - xlstr.bytes.len())]
+    _unused: Vec<u8>,
+
+    #[br(ignore)]
+    pub author: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.author = super::xlstring(encoding, self.xlstr.hbyte, &self.xlstr.bytes);
+    }
+}
@@ -0,0 +1,21 @@
+use binrw::BinRead;
+
+// 2.4.181
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 22))]
+    _len: u16,
+
+    // FtCmo
+    #[br(assert(_ft == 0x0015))]
+    _ft: u16,
+    #[br(assert(_cb == 0x0012))]
+    _cb: u16,
+    pub ot: u16, // 0x0019: comment
+    pub id: u16,
+    _flags: u16,
+    _unused: [u8; 12],
+
+    #[br(count = _len - 22)]
+    _subrecords: Vec<u8>,
+}
@@ -0,0 +1,20 @@
+use binrw::BinRead;
+
+// 2.4.122 HorizontalPageBreaks, 2.4.343 VerticalPageBreaks
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _cbrk: u16,
+    #[br(count = _cbrk)]
+    pub breaks: Vec<Break>,
+}
+
+// 2.5.151 HorzBrk, 2.5.273 VertBrk
+#[derive(Debug, BinRead)]
+pub struct Break {
+    /// Zero-based row or column starting the new page
+    pub index: u16,
+    pub start: u16,
+    pub end: u16,
+}
@@ -0,0 +1,16 @@
+use binrw::BinRead;
+
+// 2.4.189
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 9))]
+    _len: u16,
+
+    pub x: u16,
+    pub y: u16,
+    pub row_top: u16,
+    pub col_left: u16,
+    pub pnn_acct: u8,
+    #[br(count = _len - 9)]
+    _reserved: Vec<u8>,
+}
@@ -0,0 +1,11 @@
+use binrw::BinRead;
+
+// 2.4.135 HCenter, 2.4.342 VCenter, 2.4.202 PrintGrid, 2.4.203 PrintRowCol
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 2))]
+    _len: u16,
+
+    #[br(map = |x: u16| x == 0x0001)]
+    pub value: bool,
+}
@@ -14,7 +14,7 @@ pub struct Data {
 
     #[br(calc = _rkrec.ixfe())]
     pub ixfe: u16,
-    #[br(calc = { let v = _rkrec.num() as f64; if _rkrec.fx100() { v / 100.0 } else { v } })]
+    #[br(calc = _rkrec.value())]
     pub num: f64,
 }
 
@@ -24,10 +24,88 @@ pub struct Data {
 pub struct RkRec {
     #[skip(setters)]
     pub ixfe: u16,
+    // 2.5.217 RkNumber
+    #[skip(setters)]
+    fx100: bool,
     #[skip(setters)]
-    pub fx100: bool,
-    #[skip]
     fint: bool,
     #[skip(setters)]
-    pub num: B30,
+    num: B30,
+}
+
+impl RkRec {
+    pub fn value(&self) -> f64 {
+        let bits = self.num() << 2;
+        let num = if self.fint() {
+            // signed 30-bit integer
+            (bits as i32 >> 2) as f64
+        } else {
+            // the high 30 bits of an IEEE-754 double
+            f64::from_bits((bits as u64) << 32)
+        };
+        if self.fx100() { num / 100.0 } else { num }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RkRec;
+
+    fn rk(rk: u32) -> f64 {
+        let [a, b, c, d] = rk.to_le_bytes();
+        RkRec::from_bytes([0x0F, 0x00, a, b, c, d]).value()
+    }
+
+    fn rk_int(num: i32, fx100: bool) -> u32 {
+        (num << 2) as u32 | 0x02 | fx100 as u32
+    }
+
+    fn rk_float(num: f64, fx100: bool) -> u32 {
+        (num.to_bits() >> 32) as u32 & 0xFFFF_FFFC | fx100 as u32
+    }
+
+    #[test]
+    fn integer() {
+        assert_eq!(rk(rk_int(0, false)), 0.0);
+        assert_eq!(rk(rk_int(123, false)), 123.0);
+        assert_eq!(rk(rk_int(0x1FFF_FFFF, false)), 536_870_911.0);
+    }
+
+    #[test]
+    fn negative_integer() {
+        assert_eq!(rk(rk_int(-5, false)), -5.0);
+        assert_eq!(rk(rk_int(-0x2000_0000, false)), -536_870_912.0);
+    }
+
+    #[test]
+    fn integer_x100() {
+        assert_eq!(rk(rk_int(12345, true)), 123.45);
+        assert_eq!(rk(rk_int(-150, true)), -1.5);
+    }
+
+    #[test]
+    fn float() {
+        assert_eq!(rk(rk_float(1.25, false)), 1.25);
+        assert_eq!(rk(rk_float(65536.5, false)), 65536.5);
+        assert_eq!(rk(0x3FF4_0000), 1.25);
+    }
+
+    #[test]
+    fn negative_float() {
+        assert_eq!(rk(rk_float(-2.5, false)), -2.5);
+        assert_eq!(rk(0xC004_0000), -2.5);
+    }
+
+    #[test]
+    fn float_x100() {
+        assert_eq!(rk(rk_float(125.0, true)), 1.25);
+        assert_eq!(rk(rk_float(-1234.0, true)), -12.34);
+    }
+
+    #[test]
+    fn ixfe() {
+        let rec = RkRec::from_bytes([0x2A, 0x01, 0x02, 0x00, 0x00, 0x00]);
+        assert_eq!(rec.ixfe(), 0x012A);
+        assert_eq!(rec.value(), 0.0);
+    }
 }
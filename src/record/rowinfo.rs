@@ -29,22 +29,22 @@ pub struct Data {
 #[br(map = Self::from_bytes,
     assert(!self.reserved2() && self.reserved3() == 1))]
 pub struct Info {
-    #[skip]
-    outlevel: B3,
+    #[skip(setters)]
+    pub outlevel: B3,
     #[skip(setters)]
     reserved2: bool,
-    #[skip]
-    collapsed: bool,
+    #[skip(setters)]
+    pub collapsed: bool,
     #[skip(setters)]
     pub hidden: bool,
     #[skip]
     user_set: bool,
-    #[skip]
-    formatted: bool,
+    #[skip(setters)]
+    pub formatted: bool,
     #[skip(setters)]
     reserved3: u8,
-    #[skip]
-    ixfe: B12,
+    #[skip(setters)]
+    pub ixfe: B12,
     #[skip(setters)]
     pub top_bdr: bool,
     #[skip]
@@ -0,0 +1,11 @@
+use binrw::BinRead;
+
+// 2.4.247
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 4))]
+    _len: u16,
+
+    pub nscl: i16,
+    pub dscl: i16,
+}
@@ -0,0 +1,19 @@
+use super::RefU;
+use binrw::BinRead;
+
+// 2.4.248
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub pnn: u8,
+    pub row_act: u16,
+    pub col_act: u16,
+    _iref_act: u16,
+    #[br(assert(_len as usize >= 9 + 6 * _cref as usize))]
+    _cref: u16,
+    #[br(count = _cref)]
+    pub refs: Vec<RefU>,
+    #[br(count = _len as usize - 9 - 6 * _cref as usize)]
+    _unused: Vec<u8>,
+}
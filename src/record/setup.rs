@@ -0,0 +1,55 @@
+use binrw::BinRead;
+use modular_bitfield::{
+    bitfield,
+    prelude::{B1, B2, B4},
+};
+
+// 2.4.257
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 34))]
+    _len: u16,
+
+    pub paper_size: u16,
+    pub scale: u16,
+    pub page_start: i16,
+    pub fit_width: u16,
+    pub fit_height: u16,
+    pub info: Info,
+    _res: u16,
+    _vres: u16,
+    pub num_hdr: f64, // inches
+    pub num_ftr: f64,
+    _copies: u16,
+}
+
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Info {
+    #[skip]
+    left_to_right: bool,
+    #[skip(setters)]
+    pub portrait: bool,
+    // paper size, scale, orientation, resolution and copies are undefined
+    #[skip(setters)]
+    pub no_pls: bool,
+    #[skip]
+    no_color: bool,
+    #[skip]
+    draft: bool,
+    #[skip]
+    notes: bool,
+    #[skip(setters)]
+    pub no_orient: bool,
+    #[skip(setters)]
+    pub use_page: bool,
+    #[skip]
+    __: B1,
+    #[skip]
+    end_notes: bool,
+    #[skip]
+    errors: B2,
+    #[skip]
+    __: B4,
+}
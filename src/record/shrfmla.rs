@@ -0,0 +1,20 @@
+use super::RefU;
+use binrw::BinRead;
+
+// 2.4.260
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    pub r#ref: RefU,
+    _reserved: u8,
+    _cuse: u8,
+
+    // SharedParsedFormula
+    #[br(assert(_len as usize >= 10 + _cce as usize))]
+    _cce: u16,
+    #[br(count = _cce)]
+    pub rgce: Vec<u8>,
+    #[br(count = _len as usize - 10 - _cce as usize)]
+    pub rgcb: Vec<u8>,
+}
@@ -1,11 +1,6 @@
-use super::Data as ContinueData;
-use crate::error::Result;
-use binrw::{
-    BinRead,
-    helpers::{until, until_eof},
-};
+use crate::error::{Error, Result};
+use binrw::BinRead;
 use encoding_rs::Encoding;
-use std::io::Cursor;
 
 // 2.4.265
 #[derive(Debug, BinRead)]
@@ -18,63 +13,240 @@ pub struct Data {
     #[br(count = _len - 8)]
     bytes: Vec<u8>,
 
-    #[br(parse_with = until(|cd: &ContinueData| cd.r#type != 0x003C))]
-    continues: Vec<ContinueData>,
+    // the strings go on in the following Continue records
+    #[br(parse_with = super::continues, args(0x003C))]
+    continues: Vec<Vec<u8>>,
 
     #[br(ignore)]
-    pub strs: Vec<String>,
+    pub strs: Vec<SstString>,
+}
+
+/// Shared string, with its formatting runs and phonetic text.
+#[derive(Debug, Default)]
+pub struct SstString {
+    pub text: String,
+    pub runs: Vec<(u16, u16)>, // FormatRun(ich, ifnt)
+    pub phonetic: Option<String>,
 }
 
 impl Data {
     pub fn decode(&mut self, encoding: &'static Encoding) -> Result<()> {
-        let mut bytes = self.bytes.clone();
-        let continue_bytes = self
-            .continues
-            .iter()
-            .flat_map(|c| c.bytes.clone())
-            .collect::<Vec<_>>();
-        bytes.extend(continue_bytes);
+        let mut segments = Segments {
+            segments: std::iter::once(&self.bytes)
+                .chain(&self.continues)
+                .map(Vec::as_slice)
+                .collect(),
+            index: 0,
+            pos: 0,
+        };
+
+        while !segments.is_empty() {
+            let s = segments.string(encoding).ok_or_else(|| {
+                Error::msg(format!("SST string {} is truncated", self.strs.len()))
+            })?;
+            self.strs.push(s);
+        }
+
+        #[cfg(feature = "tracing")]
+        if self.strs.len() != self._unique as usize {
+            tracing::warn!(
+                "SST has {} strings, expected {}\n",
+                self.strs.len(),
+                self._unique
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Bytes of the SST record and its Continue records, read across the record boundaries.
+struct Segments<'a> {
+    segments: Vec<&'a [u8]>,
+    index: usize,
+    pos: usize,
+}
+
+impl Segments<'_> {
+    fn is_empty(&self) -> bool {
+        self.segments
+            .get(self.index)
+            .is_none_or(|segment| self.pos >= segment.len())
+            && self
+                .segments
+                .iter()
+                .skip(self.index + 1)
+                .all(|segment| segment.is_empty())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        loop {
+            let segment = self.segments.get(self.index)?;
+            if let Some(&byte) = segment.get(self.pos) {
+                self.pos += 1;
+                return Some(byte);
+            }
+            self.index += 1;
+            self.pos = 0;
+        }
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_le_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        Some(i32::from_le_bytes([
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+            self.u8()?,
+        ]))
+    }
+
+    fn bytes(&mut self, count: usize) -> Option<Vec<u8>> {
+        (0..count).map(|_| self.u8()).collect()
+    }
 
-        let mut stream = Cursor::new(bytes);
-        let xlstrs = XLStrings::read(&mut stream)?;
+    // 2.5.293 XLUnicodeRichExtendedString
+    fn string(&mut self, encoding: &'static Encoding) -> Option<SstString> {
+        let cch = self.u16()?;
+        let flags = self.u8()?;
+        let hbyte = flags & 0x01 == 0x00;
+        let hext = flags & 0x04 == 0x04;
+        let hrun = flags & 0x08 == 0x08;
+        let crun = if hrun { self.u16()? } else { 0 };
+        let cext = if hext { self.i32()? } else { 0 };
 
-        self.strs = xlstrs
-            .0
+        let units = self.chars(cch.into(), hbyte)?;
+        let bytes = units
             .iter()
-            .map(|s| super::xlstring(encoding, s.hbyte, &s.bytes))
+            .flat_map(|unit| unit.to_le_bytes())
             .collect::<Vec<_>>();
-        Ok(())
+        let runs = (0..crun)
+            .map(|_| Some((self.u16()?, self.u16()?)))
+            .collect::<Option<Vec<_>>>()?;
+        let bext = self.bytes(cext.max(0) as usize)?;
+
+        Some(SstString {
+            text: super::xlstring(encoding, false, &bytes),
+            runs,
+            phonetic: phonetic(&bext),
+        })
+    }
+
+    // characters split into a Continue record restart with an option byte, which may change the high byte flag
+    fn chars(&mut self, cch: usize, mut hbyte: bool) -> Option<Vec<u16>> {
+        let mut units = Vec::with_capacity(cch);
+        while units.len() < cch {
+            let segment = self.segments.get(self.index)?;
+            if self.pos >= segment.len() {
+                self.index += 1;
+                hbyte = self.segments.get(self.index)?.first()? & 0x01 == 0x00;
+                self.pos = 1;
+                continue;
+            }
+            if hbyte {
+                units.push(segment[self.pos].into());
+                self.pos += 1;
+            } else {
+                let unit = segment.get(self.pos..self.pos + 2)?;
+                units.push(u16::from_le_bytes([unit[0], unit[1]]));
+                self.pos += 2;
+            }
+        }
+        Some(units)
     }
 }
 
-#[derive(Debug, BinRead)]
-#[br(little)]
-pub struct XLStrings(#[br(parse_with = until_eof)] Vec<XLUnicodeRichExtendedString>);
+// 2.5.85 ExtRst, the phonetic string of its RPHSSub is always UTF-16
+fn phonetic(bext: &[u8]) -> Option<String> {
+    let u16_at = |i: usize| Some(u16::from_le_bytes([*bext.get(i)?, *bext.get(i + 1)?]));
+    // reserved, cb, Phs(ifnt, info), RPHSSub(crun, cch, LPWideString(cch, rgchData))
+    if u16_at(0)? != 0x0001 {
+        return None;
+    }
+    let cch = u16_at(12)? as usize;
+    let chars = (0..cch)
+        .map(|i| u16_at(14 + i * 2))
+        .collect::<Option<Vec<_>>>()?;
+    (cch > 0).then(|| String::from_utf16_lossy(&chars))
+}
 
-#[derive(Debug, BinRead)]
-struct XLUnicodeRichExtendedString {
-    _cch: u16,
-
-    _byte: u8,
-    #[br(calc = _byte & 0x01 == 0x00)]
-    hbyte: bool,
-    /// _reserved1 bits = 1
-    #[br(calc = _byte >> 2 & 0x01 == 0x01)]
-    _hext: bool,
-    #[br(calc = _byte >> 3 & 0x01 == 0x01)]
-    _hrun: bool,
-    /// _reserved2 bits = 4
-
-    #[br(if(_hrun))]
-    _crun: u16,
-    #[br(if(_hext))]
-    _cext: i32,
-
-    #[br(count = if hbyte { _cch } else { _cch * 2 })]
-    bytes: Vec<u8>,
+#[cfg(test)]
+mod tests {
+    use super::Data;
+    use binrw::BinRead;
+    use encoding_rs::UTF_16LE;
+    use std::io::Cursor;
 
-    #[br(if(_hrun), count = _crun)]
-    _brun: Vec<(u16, u16)>, // FormatRun(ich, ifnt)
-    #[br(if(_hext), count = _cext)]
-    _bext: Vec<u8>,
+    fn record(r#type: u16, body: &[u8]) -> Vec<u8> {
+        let mut bytes = r#type.to_le_bytes().to_vec();
+        bytes.extend((body.len() as u16).to_le_bytes());
+        bytes.extend(body);
+        bytes
+    }
+
+    #[test]
+    fn split_strings() {
+        // "ABC" compressed, then "DEF漢字" in UTF-16 after the option byte of the Continue record
+        let mut sst = vec![2, 0, 0, 0, 2, 0, 0, 0, 8, 0, 0x00];
+        sst.extend(b"ABC");
+        let mut continue1 = vec![0x01];
+        continue1.extend("DEF漢字".encode_utf16().flat_map(u16::to_le_bytes));
+        // the header of the next string starts a Continue record, its runs are split without option byte
+        let mut continue2 = vec![5, 0, 0x08, 1, 0];
+        continue2.extend(b"hello");
+        continue2.extend([2, 0]);
+        let continue3 = [6, 0];
+
+        let mut bytes = record(0x00FC, &sst)[2..].to_vec();
+        bytes.extend(record(0x003C, &continue1));
+        bytes.extend(record(0x003C, &continue2));
+        bytes.extend(record(0x003C, &continue3));
+        let ext_sst = bytes.len() as u64;
+        bytes.extend(record(0x00FF, &[8, 0]));
+
+        let mut cursor = Cursor::new(bytes);
+        let mut data = Data::read_le(&mut cursor).unwrap();
+        data.decode(UTF_16LE).unwrap();
+
+        assert_eq!(data.strs.len(), 2);
+        assert_eq!(data.strs[0].text, "ABCDEF漢字");
+        assert_eq!(data.strs[1].text, "hello");
+        assert_eq!(data.strs[1].runs, [(2, 6)]);
+        // the ExtSST record is left to the record reader
+        assert_eq!(cursor.position(), ext_sst);
+    }
+
+    #[test]
+    fn runs_and_phonetic() {
+        // "漢字です" in UTF-16 with two runs and the ExtRst of its phonetic text "かんじ"
+        let mut ext = [0x0001u16, 22, 7, 0, 1, 2, 3]
+            .map(u16::to_le_bytes)
+            .concat();
+        ext.extend("かんじ".encode_utf16().flat_map(u16::to_le_bytes));
+        // PhRuns of the phonetic text
+        ext.extend([0u16, 0, 2].map(u16::to_le_bytes).concat());
+        let mut sst = vec![2, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0x0D, 2, 0];
+        sst.extend((ext.len() as i32).to_le_bytes());
+        sst.extend("漢字です".encode_utf16().flat_map(u16::to_le_bytes));
+        sst.extend([0u16, 5, 2, 6].map(u16::to_le_bytes).concat());
+        sst.extend(ext);
+        // a string without runs nor ExtRst
+        sst.extend([2, 0, 0x00]);
+        sst.extend(b"ok");
+
+        let mut cursor = Cursor::new(record(0x00FC, &sst)[2..].to_vec());
+        let mut data = Data::read_le(&mut cursor).unwrap();
+        data.decode(UTF_16LE).unwrap();
+
+        assert_eq!(data.strs.len(), 2);
+        assert_eq!(data.strs[0].text, "漢字です");
+        assert_eq!(data.strs[0].runs, [(0, 5), (2, 6)]);
+        // the characters of the LPWideString at offset 12 of the ExtRst, not the cch of RPHSSub
+        assert_eq!(data.strs[0].phonetic.as_deref(), Some("かんじ"));
+        assert_eq!(data.strs[1].text, "ok");
+        assert!(data.strs[1].runs.is_empty());
+        assert_eq!(data.strs[1].phonetic, None);
+    }
 }
@@ -0,0 +1,19 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.268
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    xlstr: super::XLUnicodeString,
+
+    #[br(ignore)]
+    pub content: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.content = super::xlstring(encoding, self.xlstr.hbyte, &self.xlstr.bytes);
+    }
+}
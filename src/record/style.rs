@@ -7,24 +7,27 @@ pub struct Data {
     _len: u16,
 
     #[br(map = |x: u16| x & 0x0FFF, restore_position)]
-    _ixfe: u16,
+    pub ixfe: u16,
     /// _unused bits = 3
     #[br(map = |x: u16| x >> 15 == 0x0001)]
-    _builtin: bool,
+    pub builtin: bool,
 
-    #[br(args(_builtin))]
+    #[br(args(builtin))]
     pub style: Style,
 
     #[br(restore_position)]
     _ext_magic: u16,
     #[br(if(_ext_magic == 0x0892))]
-    _ext: Option<super::styleext::Data>,
+    pub ext: Option<super::styleext::Data>,
 }
 impl Data {
     pub fn decode(&mut self, encoding: &'static Encoding) {
         if let Style::User { ustr, name } = &mut self.style {
             *name = super::xlstring(encoding, ustr.hbyte, &ustr.bytes);
         };
+        if let Some(ext) = &mut self.ext {
+            ext.decode(encoding);
+        }
     }
 }
 
@@ -33,9 +36,9 @@ impl Data {
 pub enum Style {
     #[br(pre_assert(_builtin))]
     BuiltIn {
-        _isbi: u8,
-        #[br(args(_isbi))]
-        _outlevel: OutlineLevel,
+        isbi: u8,
+        #[br(args(isbi))]
+        outlevel: OutlineLevel,
     },
     #[br(pre_assert(!_builtin))]
     User {
@@ -45,7 +48,7 @@ pub enum Style {
     },
 }
 
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, Copy, BinRead)]
 #[br(import(isbi: u8))]
 pub enum OutlineLevel {
     #[br(magic = 0x00u8, pre_assert(isbi < 0x03))]
@@ -65,3 +68,13 @@ pub enum OutlineLevel {
     #[br(magic = 0xFFu8)]
     None,
 }
+
+impl OutlineLevel {
+    /// Zero-based level of the RowLevel and ColLevel styles.
+    pub fn level(&self) -> Option<u8> {
+        match self {
+            OutlineLevel::None => None,
+            level => Some(*level as u8),
+        }
+    }
+}
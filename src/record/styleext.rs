@@ -23,20 +23,20 @@ pub struct Data {
 
     _byte1: u8,
     #[br(calc = _byte1 & 0x01 == 0x01)]
-    _builtin: bool,
+    pub builtin: bool,
     #[br(calc = _byte1 >> 1 & 0x01 == 0x01)]
-    _hidden: bool,
-    #[br(calc = _byte1 >> 2 & 0x01 == 0x01, assert(if _custom { _builtin } else { true }))]
+    pub hidden: bool,
+    #[br(calc = _byte1 >> 2 & 0x01 == 0x01, assert(if _custom { builtin } else { true }))]
     _custom: bool,
     // reserved bits = 5
-    _catagory: Category,
+    pub category: Category,
 
-    #[br(args(_builtin))]
+    #[br(args(builtin))]
     _builtin_data: BuiltInData,
 
     _wstr: LPWideString,
     #[br(ignore)]
-    _name: String,
+    pub name: String,
 
     // xfProps
     #[br(assert(_reserved2 == 0))]
@@ -47,8 +47,8 @@ pub struct Data {
 }
 
 impl Data {
-    pub fn _decode(&mut self, ecoding: &'static Encoding) {
-        self._name = super::xlstring(ecoding, false, &self._wstr._bytes);
+    pub fn decode(&mut self, ecoding: &'static Encoding) {
+        self.name = super::xlstring(ecoding, false, &self._wstr._bytes);
         self._props.iter_mut().for_each(|prop| match prop {
             XFProp::FontName {
                 _cb: _,
@@ -69,7 +69,7 @@ impl Data {
     }
 }
 
-#[derive(Debug, BinRead)]
+#[derive(Debug, Clone, Copy, BinRead)]
 pub enum Category {
     #[br(magic = 0x00u8)]
     Custom,
@@ -0,0 +1,67 @@
+use binrw::BinRead;
+use encoding_rs::Encoding;
+
+// 2.4.271
+#[derive(Debug, BinRead)]
+pub struct Data {
+    _len: u16,
+
+    _ctab: u16,
+    _cch: u16,
+
+    #[br(args(_ctab, _cch))]
+    pub book: Book,
+
+    // ExternName records following this one
+    #[br(ignore)]
+    pub names: Vec<String>,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        if let Book::External {
+            _hbyte,
+            _bytes,
+            path,
+            _sheets,
+            sheets,
+        } = &mut self.book
+        {
+            // encoded path, 2.5.277 VirtualPath
+            *path = super::xlstring(encoding, *_hbyte, _bytes)
+                .chars()
+                .filter_map(|c| match c {
+                    '\u{03}' => Some('/'),
+                    '\u{01}'..='\u{08}' => None,
+                    c => Some(c),
+                })
+                .collect();
+            *sheets = _sheets
+                .iter()
+                .map(|s| super::xlstring(encoding, s.hbyte, &s.bytes))
+                .collect();
+        }
+    }
+}
+
+#[derive(Debug, BinRead)]
+#[br(import(ctab: u16, cch: u16))]
+pub enum Book {
+    #[br(pre_assert(cch == 0x0401))]
+    Internal,
+    #[br(pre_assert(cch == 0x3A01))]
+    AddIn,
+    External {
+        #[br(map = |x: u8| x == 0x00)]
+        _hbyte: bool,
+        #[br(count = if _hbyte { cch as usize } else { cch as usize * 2 })]
+        _bytes: Vec<u8>,
+        #[br(ignore)]
+        path: String,
+
+        #[br(count = ctab)]
+        _sheets: Vec<super::XLUnicodeString>,
+        #[br(ignore)]
+        sheets: Vec<String>,
+    },
+}
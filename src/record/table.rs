@@ -0,0 +1,26 @@
+use super::RefU;
+use binrw::BinRead;
+
+// 2.4.319
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 16))]
+    _len: u16,
+
+    pub r#ref: RefU,
+
+    _flags: u16,
+    #[br(calc = _flags & 0x0004 == 0x0004)]
+    pub row_input: bool, // fRw
+    #[br(calc = _flags & 0x0008 == 0x0008)]
+    pub two_inputs: bool, // fTbl2
+    #[br(calc = _flags & 0x0010 == 0x0010)]
+    pub deleted1: bool,
+    #[br(calc = _flags & 0x0020 == 0x0020)]
+    pub deleted2: bool,
+
+    pub row_input1: u16,
+    pub col_input1: u16,
+    pub row_input2: u16,
+    pub col_input2: u16,
+}
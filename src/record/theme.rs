@@ -0,0 +1,27 @@
+use binrw::BinRead;
+
+// 2.4.326
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 16))]
+    _len: u16,
+
+    // frtHeader
+    #[br(assert(_rt == 0x0896))]
+    _rt: u16,
+    _flags: u16,
+    _reserved: u64,
+
+    // 124226 for the default theme, without content
+    pub version: u32,
+
+    #[br(count = _len - 16)]
+    bytes: Vec<u8>,
+    // ContinueFrt records, after their frtHeaderOld
+    #[br(parse_with = super::continues, args(0x0812))]
+    continues: Vec<Vec<u8>>,
+
+    // theme part of an Office Open XML package
+    #[br(calc = bytes.iter().chain(continues.iter().flat_map(|c| c.get(4..).unwrap_or_default())).copied().collect())]
+    pub content: Vec<u8>,
+}
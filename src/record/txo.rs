@@ -0,0 +1,88 @@
+use super::Data as ContinueData;
+use binrw::{BinRead, BinResult};
+use encoding_rs::Encoding;
+
+// 2.4.329
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 16))]
+    _len: u16,
+
+    _flags: u16,
+    _rot: u16,
+    _control_info: [u8; 6],
+    _cch_text: u16,
+    _cb_runs: u16,
+    _ifnt_empty: u16,
+    #[br(count = _len - 16)]
+    _fmla: Vec<u8>,
+
+    // XLUnicodeStringNoCch split into Continue records, each with its own high byte flag
+    #[br(parse_with = text_parts, args(_cch_text))]
+    parts: Vec<(bool, Vec<u8>)>,
+    // TxORuns, the last run marks the end of the text
+    #[br(if(_cch_text > 0), parse_with = runs, args(_cb_runs))]
+    pub runs: Vec<(u16, u16)>,
+
+    #[br(ignore)]
+    pub text: String,
+}
+
+impl Data {
+    pub fn decode(&mut self, encoding: &'static Encoding) {
+        self.text = self
+            .parts
+            .iter()
+            .map(|(hbyte, bytes)| super::xlstring(encoding, *hbyte, bytes))
+            .collect();
+    }
+}
+
+#[binrw::parser(reader, endian)]
+fn text_parts(cch: u16) -> BinResult<Vec<(bool, Vec<u8>)>> {
+    let mut parts = Vec::new();
+    let mut count = 0;
+    while count < cch as usize {
+        let part = continue_data(reader, endian)?;
+        let Some((&flag, bytes)) = part.bytes.split_first() else {
+            break;
+        };
+        let hbyte = flag & 0x01 == 0x00;
+        count += if hbyte { bytes.len() } else { bytes.len() / 2 };
+        parts.push((hbyte, bytes.to_vec()));
+    }
+    Ok(parts)
+}
+
+#[binrw::parser(reader, endian)]
+fn runs(cb: u16) -> BinResult<Vec<(u16, u16)>> {
+    let mut bytes = Vec::new();
+    while bytes.len() < cb as usize {
+        bytes.extend(continue_data(reader, endian)?.bytes);
+    }
+    // Run: FormatRun(ich, ifnt) and 4 unused bytes
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|run| {
+            (
+                u16::from_le_bytes([run[0], run[1]]),
+                u16::from_le_bytes([run[2], run[3]]),
+            )
+        })
+        .collect())
+}
+
+fn continue_data<R: binrw::io::Read + binrw::io::Seek>(
+    reader: &mut R,
+    endian: binrw::Endian,
+) -> BinResult<ContinueData> {
+    let pos = reader.stream_position()?;
+    let data = ContinueData::read_options(reader, endian, ())?;
+    if data.r#type != 0x003C {
+        return Err(binrw::Error::AssertFail {
+            pos,
+            message: format!("expected Continue, got 0x{:04X}", data.r#type),
+        });
+    }
+    Ok(data)
+}
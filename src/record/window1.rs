@@ -0,0 +1,18 @@
+use binrw::BinRead;
+
+// 2.4.345
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 18))]
+    _len: u16,
+
+    _x_wn: i16,
+    _y_wn: i16,
+    _dx_wn: i16,
+    _dy_wn: i16,
+    _flags: u16,
+    pub itab_cur: u16,
+    _itab_first: u16,
+    _ctab_sel: u16,
+    _w_tab_ratio: u16,
+}
@@ -0,0 +1,55 @@
+use binrw::BinRead;
+use modular_bitfield::{bitfield, prelude::B4};
+
+// 2.4.346
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len >= 10))]
+    _len: u16,
+
+    pub info: Info,
+    pub row_top: u16,
+    pub col_left: u16,
+    _icv_hdr: u16,
+    _reserved2: u16,
+
+    // absent in chart sheets
+    #[br(if(_len >= 14))]
+    pub scale_slv: u16,
+    #[br(if(_len >= 14))]
+    pub scale_normal: u16,
+    #[br(count = if _len >= 14 { _len - 14 } else { _len - 10 })]
+    _unused: Vec<u8>,
+}
+
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Info {
+    #[skip(setters)]
+    pub dsp_fmla: bool,
+    #[skip(setters)]
+    pub dsp_grid: bool,
+    #[skip(setters)]
+    pub dsp_rw_col: bool,
+    #[skip(setters)]
+    pub frozen: bool,
+    #[skip(setters)]
+    pub dsp_zeros: bool,
+    #[skip]
+    default_hdr: bool,
+    #[skip(setters)]
+    pub right_to_left: bool,
+    #[skip]
+    dsp_guts: bool,
+    #[skip(setters)]
+    pub frozen_no_split: bool,
+    #[skip(setters)]
+    pub selected: bool,
+    #[skip]
+    paged: bool,
+    #[skip(setters)]
+    pub slv: bool,
+    #[skip]
+    __: B4,
+}
@@ -0,0 +1,46 @@
+use binrw::BinRead;
+use modular_bitfield::{
+    bitfield,
+    prelude::{B1, B2, B3},
+};
+
+// 2.4.351
+#[derive(Debug, BinRead)]
+pub struct Data {
+    #[br(assert(_len == 2))]
+    _len: u16,
+
+    pub info: Info,
+}
+
+#[bitfield]
+#[derive(Debug, BinRead)]
+#[br(map = Self::from_bytes)]
+pub struct Info {
+    #[skip]
+    show_auto_breaks: bool,
+    #[skip]
+    __: B3,
+    #[skip]
+    dialog: bool,
+    #[skip]
+    apply_styles: bool,
+    #[skip(setters)]
+    pub row_sums_below: bool,
+    #[skip(setters)]
+    pub col_sums_right: bool,
+    #[skip(setters)]
+    pub fit_to_page: bool,
+    #[skip]
+    __: B1,
+    #[skip]
+    __: B2,
+    #[skip]
+    sync_horiz: bool,
+    #[skip]
+    sync_vert: bool,
+    #[skip]
+    alt_expr_eval: bool,
+    #[skip]
+    alt_formula_entry: bool,
+}
@@ -40,11 +40,11 @@ pub struct Protection {
     #[skip(setters)]
     pub hidden: bool, //  1 bit
     #[skip(setters)]
-    is_style: bool, //  1 bit
+    pub is_style: bool, //  1 bit
     #[skip(setters)]
     prefix123: bool, //  1 bit
     #[skip(setters)]
-    parent_ixfe: B12, // 12 bits
+    pub parent_ixfe: B12, // 12 bits
 }
 
 #[bitfield]
@@ -63,8 +63,8 @@ pub struct Alignment {
     just_last: bool, // 1 bit
     #[skip(setters)]
     pub text_rotation: u8, // 8 bits
-    #[skip]
-    indent: B4, // 4 bits
+    #[skip(setters)]
+    pub indent: B4, // 4 bits
     #[skip]
     shrink_fit: bool, // 1 bit
     #[skip(setters)]
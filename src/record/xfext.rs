@@ -14,8 +14,8 @@ pub struct Data {
     #[br(assert(_reserved1 == 0))]
     _reserved1: u16,
 
-    #[br(assert(_ixfe <= 4050))]
-    _ixfe: u16,
+    #[br(assert(ixfe <= 4050))]
+    pub ixfe: u16,
 
     #[br(assert(_reserved2 == 0))]
     _reserved2: u16,
@@ -23,41 +23,41 @@ pub struct Data {
     _cexts: u16,
 
     #[br(count = _cexts)]
-    _exts: Vec<ExtProp>,
+    pub exts: Vec<ExtProp>,
 }
 
 #[derive(Debug, BinRead)]
-enum ExtProp {
+pub enum ExtProp {
     #[br(magic = 0x0004u16)]
-    Foreground { _cb: u16, _color: FullColorExt },
+    Foreground { _cb: u16, color: FullColorExt },
     #[br(magic = 0x0005u16)]
-    Background { _cb: u16, _color: FullColorExt },
+    Background { _cb: u16, color: FullColorExt },
     #[br(magic = 0x0006u16)]
-    Gradient { _cb: u16, _fill: XFExtGradient },
+    Gradient { _cb: u16, fill: XFExtGradient },
     #[br(magic = 0x0007u16)]
-    TopBorder { _cb: u16, _color: FullColorExt },
+    TopBorder { _cb: u16, color: FullColorExt },
     #[br(magic = 0x0008u16)]
-    BottomBorder { _cb: u16, _color: FullColorExt },
+    BottomBorder { _cb: u16, color: FullColorExt },
     #[br(magic = 0x0009u16)]
-    LeftBorder { _cb: u16, _color: FullColorExt },
+    LeftBorder { _cb: u16, color: FullColorExt },
     #[br(magic = 0x000Au16)]
-    RightBorder { _cb: u16, _color: FullColorExt },
+    RightBorder { _cb: u16, color: FullColorExt },
     #[br(magic = 0x000Bu16)]
-    DiagonalBorder { _cb: u16, _color: FullColorExt },
+    DiagonalBorder { _cb: u16, color: FullColorExt },
     #[br(magic = 0x000Du16)]
-    Text { _cb: u16, _color: FullColorExt },
+    Text { _cb: u16, color: FullColorExt },
     #[br(magic = 0x000Eu16)]
-    Font { _cb: u16, _scheme: FontScheme },
+    Font { _cb: u16, scheme: FontScheme },
     #[br(magic = 0x000Fu16)]
     Indent {
         _cb: u16,
-        #[br(assert(_level <= 250))]
-        _level: u16,
+        #[br(assert(level <= 250))]
+        level: u16,
     },
 }
 
 #[derive(Debug, BinRead)]
-enum XColorType {
+pub enum XColorType {
     #[br(magic = 0x0000u16)] // Automatic color
     Auto,
     #[br(magic = 0x0001u16)] // Indexed color
@@ -72,14 +72,14 @@ enum XColorType {
 
 #[derive(Debug, BinRead)]
 pub struct LongRGBA {
-    _red: u8,
-    _green: u8,
-    _blue: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
     _alpha: u8,
 }
 
-#[derive(Debug, BinRead)]
-enum ColorTheme {
+#[derive(Debug, Clone, Copy, BinRead)]
+pub enum ColorTheme {
     #[br(magic = 0x0000_0000u32)]
     Dark1,
     #[br(magic = 0x0000_0001u32)]
@@ -108,32 +108,32 @@ enum ColorTheme {
 
 #[derive(Debug, BinRead)]
 #[br(import(color_type: &XColorType))]
-enum ColorValue {
+pub enum ColorValue {
     #[br(pre_assert(matches!(color_type, &XColorType::Auto | &XColorType::None)), assert(_val == 0))]
     None { _val: u32 },
     #[br(pre_assert(matches!(color_type, &XColorType::Indexed)))]
     Index {
-        #[br(map = |x: u32| x & 0x7F)]
-        _val: u32,
+        #[br(map = |x: u32| (x & 0x7F) as u16)]
+        icv: u16,
     },
     #[br(pre_assert(matches!(color_type, &XColorType::Rgb)))]
-    Rgba { _color: LongRGBA },
+    Rgba { color: LongRGBA },
     #[br(pre_assert(matches!(color_type, &XColorType::Themed)))]
-    Theme { _theme: ColorTheme },
+    Theme { theme: ColorTheme },
 }
 
 #[derive(Debug, BinRead)]
-struct FullColorExt {
+pub struct FullColorExt {
     _color_type: XColorType,
-    _tint_shade: i16, // darken <--> lighten
+    pub tint_shade: i16, // darken <--> lighten
 
     #[br(args(&_color_type))]
-    _color_value: ColorValue,
+    pub color_value: ColorValue,
     _unused: u64, // undefined, ignored.
 }
 
-#[derive(Debug, BinRead)]
-enum GradientType {
+#[derive(Debug, PartialEq, Eq, BinRead)]
+pub enum GradientType {
     #[br(magic = 0x00000000u32)]
     Linear,
     #[br(magic = 0x00000001u32)]
@@ -142,38 +142,39 @@ enum GradientType {
 
 #[derive(Debug, BinRead)]
 pub struct XFPropGradient {
-    _gradient_type: GradientType,
-    _degree: f64,
-    #[br(assert(matches!(_fill2left, 0.0..=1.0)))]
-    _fill2left: f64,
-    #[br(assert(matches!(_fill2right, 0.0..=1.0)))]
-    _fill2right: f64,
-    #[br(assert(matches!(_fill2top, 0.0..=1.0)))]
-    _fill2top: f64,
-    #[br(assert(matches!(_fill2bottom, 0.0..=1.0)))]
-    _fill2bottom: f64,
+    pub gradient_type: GradientType,
+    pub degree: f64,
+    #[br(assert(matches!(fill2left, 0.0..=1.0)))]
+    pub fill2left: f64,
+    #[br(assert(matches!(fill2right, 0.0..=1.0)))]
+    pub fill2right: f64,
+    #[br(assert(matches!(fill2top, 0.0..=1.0)))]
+    pub fill2top: f64,
+    #[br(assert(matches!(fill2bottom, 0.0..=1.0)))]
+    pub fill2bottom: f64,
 }
 
 #[derive(Debug, BinRead)]
-struct GradStop {
+pub struct GradStop {
     _color_type: XColorType,
-    _value: u32, // todo
-    #[br(assert(matches!(_position, 0.0..=1.0)))]
-    _position: f64,
-    #[br(assert(matches!(_position, -1.0..=1.0)))]
-    _tint: f64, // darken <--> lighten
+    #[br(args(&_color_type))]
+    pub color_value: ColorValue,
+    #[br(assert(matches!(position, 0.0..=1.0)))]
+    pub position: f64,
+    #[br(assert(matches!(tint, -1.0..=1.0)))]
+    pub tint: f64, // darken <--> lighten
 }
 
 #[derive(Debug, BinRead)]
-struct XFExtGradient {
-    _gradient: XFPropGradient,
+pub struct XFExtGradient {
+    pub gradient: XFPropGradient,
     _cstops: u32,
     #[br(count = _cstops)]
-    _grad_stops: Vec<GradStop>,
+    pub grad_stops: Vec<GradStop>,
 }
 
 #[derive(Debug, BinRead)]
-enum FontScheme {
+pub enum FontScheme {
     #[br(magic = 0x00u8)]
     None,
     #[br(magic = 0x01u8)]
@@ -0,0 +1,974 @@
+use super::{
+    error::Result,
+    model::{Global, Shared},
+    reader::WorkbookReader,
+    record::{boundsheet8, colinfo, mergecells::Ref8, rowinfo, styleext},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::File,
+    io::{Cursor, Read, Seek},
+    path::Path,
+};
+
+/// Workbook read from an XLS file, independent of any XLSX representation.
+#[derive(Debug)]
+pub struct Workbook {
+    pub(crate) global: Global,
+    pub(crate) sheets: Vec<Sheet>,
+    pub(crate) defined_names: Vec<DefinedName>,
+    pub(crate) styles: Vec<CellStyle>,
+    // index of the worksheet shown when the workbook is opened
+    pub(crate) active: Option<usize>,
+}
+
+impl Workbook {
+    /// Opens and reads an XLS file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XLS file to open
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook containing the file contents on success, or an error on failure
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_from_reader(File::open(path)?)
+    }
+
+    /// Reads in-memory XLS bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Content of the XLS file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook containing the file contents on success, or an error on failure
+    pub fn open_from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::open_from_reader(Cursor::new(bytes))
+    }
+
+    /// Reads XLS content from any reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook containing the file contents on success, or an error on failure
+    pub fn open_from_reader<R: Read + Seek>(reader: R) -> Result<Self> {
+        WorkbookReader::new(reader)?.into_workbook()
+    }
+
+    /// Opens and reads an encrypted XLS file.
+    ///
+    /// The default password `VelvetSweatshop` is tried when `password` doesn't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the XLS file to open
+    /// * `password` - Password to open the workbook
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook containing the file contents on success, or an error on failure
+    pub fn open_with_password(path: impl AsRef<Path>, password: &str) -> Result<Self> {
+        Self::open_from_reader_with_password(File::open(path)?, password)
+    }
+
+    /// Reads encrypted XLS content from any reader.
+    ///
+    /// The default password `VelvetSweatshop` is tried when `password` doesn't match.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+    /// * `password` - Password to open the workbook
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Workbook>` - Workbook containing the file contents on success, or an error on failure
+    pub fn open_from_reader_with_password<R: Read + Seek>(
+        reader: R,
+        password: &str,
+    ) -> Result<Self> {
+        WorkbookReader::new_with_password(reader, password)?.into_workbook()
+    }
+
+    /// Worksheets in the order of the workbook.
+    pub fn sheets(&self) -> &[Sheet] {
+        &self.sheets
+    }
+
+    /// Worksheet with the given name, if any.
+    pub fn sheet(&self, name: &str) -> Option<&Sheet> {
+        self.sheets.iter().find(|sheet| sheet.name == name)
+    }
+
+    /// Named ranges and formulas, including print areas, print titles and filter ranges.
+    pub fn defined_names(&self) -> &[DefinedName] {
+        &self.defined_names
+    }
+
+    /// Named cell styles, built-in and user-defined.
+    pub fn styles(&self) -> &[CellStyle] {
+        &self.styles
+    }
+
+    /// Named cell style of an XF, the style itself for a style XF, or the parent style of a cell XF.
+    ///
+    /// # Arguments
+    ///
+    /// * `xf` - Index of the XF record, e.g. `Cell::xf`
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&CellStyle>` - Named cell style, or `None` if the XF doesn't exist or has no style
+    pub fn cell_style(&self, xf: u16) -> Option<&CellStyle> {
+        let ixfe = self.global.style_xf(xf)?;
+        self.styles.iter().find(|style| style.xf == ixfe)
+    }
+
+    /// Worksheet shown when the workbook is opened, if it is a worksheet.
+    pub fn active_sheet(&self) -> Option<&Sheet> {
+        self.active.and_then(|index| self.sheets.get(index))
+    }
+
+    /// Whether the workbook uses the 1904 date system.
+    ///
+    /// `CellValue::DateTime` serials are already converted to the 1900 date system.
+    pub fn is_date1904(&self) -> bool {
+        self.global.date1904
+    }
+}
+
+/// Worksheet and its cells, keyed by zero-based `(row, col)`.
+#[derive(Debug)]
+pub struct Sheet {
+    pub(crate) name: String,
+    pub(crate) state: SheetState,
+    pub(crate) cells: BTreeMap<(u16, u16), Cell>,
+    // layout and formula context used by the XLSX conversion
+    pub(crate) colinfos: Vec<colinfo::Data>,
+    pub(crate) rowinfos: Vec<rowinfo::Data>,
+    pub(crate) default_row_height: Option<u16>,
+    pub(crate) default_col_width: Option<u16>,
+    pub(crate) merges: Vec<Ref8>,
+    pub(crate) shared: HashMap<(u16, u16), Shared>,
+    pub(crate) notes: Vec<Note>,
+    pub(crate) hyperlinks: Vec<Hyperlink>,
+    pub(crate) view: SheetView,
+    pub(crate) page_setup: PageSetup,
+    pub(crate) conditional_formats: Vec<ConditionalFormat>,
+    pub(crate) data_validations: Vec<DataValidation>,
+    pub(crate) auto_filter: Option<AutoFilter>,
+    pub(crate) outline: Outline,
+    pub(crate) formula_errors: Vec<FormulaError>,
+}
+
+impl Sheet {
+    pub(crate) fn new(name: String, state: SheetState) -> Self {
+        Sheet {
+            name,
+            state,
+            cells: BTreeMap::new(),
+            colinfos: Vec::new(),
+            rowinfos: Vec::new(),
+            default_row_height: None,
+            default_col_width: None,
+            merges: Vec::new(),
+            shared: HashMap::new(),
+            notes: Vec::new(),
+            hyperlinks: Vec::new(),
+            view: SheetView::default(),
+            page_setup: PageSetup::default(),
+            conditional_formats: Vec::new(),
+            data_validations: Vec::new(),
+            auto_filter: None,
+            outline: Outline::default(),
+            formula_errors: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state(&self) -> SheetState {
+        self.state
+    }
+
+    /// Cells in row order, then column order.
+    pub fn cells(&self) -> impl Iterator<Item = &Cell> {
+        self.cells.values()
+    }
+
+    /// Cell at the zero-based `row` and `col`, if any.
+    pub fn cell(&self, row: u16, col: u16) -> Option<&Cell> {
+        self.cells.get(&(row, col))
+    }
+
+    /// Height of the rows without a ROW record in twips, from the DEFAULTROWHEIGHT record.
+    pub fn default_row_height(&self) -> Option<u16> {
+        self.default_row_height
+    }
+
+    /// Width of the columns without a COLINFO record in characters, from the DEFCOLWIDTH record.
+    pub fn default_col_width(&self) -> Option<u16> {
+        self.default_col_width
+    }
+
+    /// Cell notes, also known as comments.
+    pub fn notes(&self) -> &[Note] {
+        &self.notes
+    }
+
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        &self.hyperlinks
+    }
+
+    /// Window settings of the worksheet, e.g. frozen panes and zoom.
+    pub fn view(&self) -> &SheetView {
+        &self.view
+    }
+
+    /// Print settings of the worksheet.
+    pub fn page_setup(&self) -> &PageSetup {
+        &self.page_setup
+    }
+
+    pub fn conditional_formats(&self) -> &[ConditionalFormat] {
+        &self.conditional_formats
+    }
+
+    pub fn data_validations(&self) -> &[DataValidation] {
+        &self.data_validations
+    }
+
+    pub fn auto_filter(&self) -> Option<&AutoFilter> {
+        self.auto_filter.as_ref()
+    }
+
+    /// Row and column groups of the worksheet.
+    pub fn outline(&self) -> &Outline {
+        &self.outline
+    }
+
+    /// Formulas that couldn't be decompiled, their cells keep the cached value.
+    pub fn formula_errors(&self) -> &[FormulaError] {
+        &self.formula_errors
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetState {
+    Visible,
+    Hidden,
+    VeryHidden,
+}
+
+impl From<boundsheet8::State> for SheetState {
+    fn from(state: boundsheet8::State) -> SheetState {
+        match state {
+            boundsheet8::State::Visible => SheetState::Visible,
+            boundsheet8::State::Hidden => SheetState::Hidden,
+            boundsheet8::State::VeryHidden => SheetState::VeryHidden,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetType {
+    Worksheet,
+    Macrosheet,
+    Chartsheet,
+    VBAModule,
+}
+
+impl From<boundsheet8::SheetType> for SheetType {
+    fn from(r#type: boundsheet8::SheetType) -> SheetType {
+        match r#type {
+            boundsheet8::SheetType::Worksheet => SheetType::Worksheet,
+            boundsheet8::SheetType::Macrosheet => SheetType::Macrosheet,
+            boundsheet8::SheetType::Chartsheet => SheetType::Chartsheet,
+            boundsheet8::SheetType::VBAModule => SheetType::VBAModule,
+        }
+    }
+}
+
+/// View settings of a worksheet, from its WINDOW2, SCL, PANE and SELECTION records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SheetView {
+    /// Whether the sheet tab is selected
+    pub selected: bool,
+    pub show_formulas: bool,
+    pub show_gridlines: bool,
+    /// Whether the row and column headings are shown
+    pub show_headers: bool,
+    pub show_zeros: bool,
+    pub right_to_left: bool,
+    pub page_break_preview: bool,
+    /// Zero-based top row of the view, or of its top left pane
+    pub top_row: u16,
+    /// Zero-based left column of the view, or of its top left pane
+    pub left_col: u16,
+    /// Zoom in percent
+    pub zoom: u16,
+    pub pane: Option<Pane>,
+    /// Zero-based `(row, col)` of the active cell
+    pub active_cell: (u16, u16),
+}
+
+impl Default for SheetView {
+    fn default() -> Self {
+        SheetView {
+            selected: false,
+            show_formulas: false,
+            show_gridlines: true,
+            show_headers: true,
+            show_zeros: true,
+            right_to_left: false,
+            page_break_preview: false,
+            top_row: 0,
+            left_col: 0,
+            zoom: 100,
+            pane: None,
+            active_cell: (0, 0),
+        }
+    }
+}
+
+/// Frozen or split panes of a worksheet view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pane {
+    /// Whether the panes are frozen, otherwise they are split
+    pub frozen: bool,
+    /// Columns of the left pane when frozen, otherwise its width in twips
+    pub x: u16,
+    /// Rows of the top pane when frozen, otherwise its height in twips
+    pub y: u16,
+    /// Zero-based top row of the bottom pane
+    pub top_row: u16,
+    /// Zero-based left column of the right pane
+    pub left_col: u16,
+    pub active: PanePosition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanePosition {
+    BottomRight,
+    TopRight,
+    BottomLeft,
+    TopLeft,
+}
+
+impl PanePosition {
+    pub(crate) fn from_pnn(pnn: u8) -> Self {
+        match pnn {
+            0 => PanePosition::BottomRight,
+            1 => PanePosition::TopRight,
+            2 => PanePosition::BottomLeft,
+            _ => PanePosition::TopLeft,
+        }
+    }
+}
+
+/// Outline grouping of a worksheet, from its GUTS, WSBOOL, ROW and COLINFO records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outline {
+    /// Highest outline level of the rows, 0 without groups
+    pub row_level: u8,
+    /// Highest outline level of the columns, 0 without groups
+    pub col_level: u8,
+    /// Whether summary rows are below their details, otherwise above
+    pub summary_below: bool,
+    /// Whether summary columns are right of their details, otherwise left
+    pub summary_right: bool,
+    /// Grouped or collapsed rows, in row order
+    pub rows: Vec<OutlineRange>,
+    /// Grouped or collapsed columns, in column order
+    pub cols: Vec<OutlineRange>,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Outline {
+            row_level: 0,
+            col_level: 0,
+            summary_below: true,
+            summary_right: true,
+            rows: Vec::new(),
+            cols: Vec::new(),
+        }
+    }
+}
+
+/// Rows or columns with the same outline state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutlineRange {
+    /// Zero-based first row or column
+    pub first: u16,
+    /// Zero-based last row or column
+    pub last: u16,
+    /// Outline level, 0 for ungrouped
+    pub level: u8,
+    /// Whether the group next to the range is collapsed, set on its summary row or column
+    pub collapsed: bool,
+}
+
+/// Print settings of a worksheet, from its SETUP, WSBOOL, margin, HEADER, FOOTER and page break records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageSetup {
+    /// Paper size, with the same values as in XLSX, `None` when not set
+    pub paper_size: Option<u16>,
+    /// Scale in percent, used when not fitting to pages
+    pub scale: u16,
+    pub landscape: bool,
+    /// Whether the sheet is scaled to `fit_width` by `fit_height` pages
+    pub fit_to_page: bool,
+    /// Pages wide, 0 for as many as needed
+    pub fit_width: u16,
+    /// Pages tall, 0 for as many as needed
+    pub fit_height: u16,
+    /// Number of the first page, `None` for automatic
+    pub first_page_number: Option<i16>,
+    /// Margins in inches
+    pub margins: Margins,
+    /// Header text, with the `&` codes of the header sections and fields
+    pub header: String,
+    /// Footer text, with the `&` codes of the footer sections and fields
+    pub footer: String,
+    pub center_horizontally: bool,
+    pub center_vertically: bool,
+    pub print_gridlines: bool,
+    /// Whether the row and column headings are printed
+    pub print_headings: bool,
+    /// Zero-based rows starting a new page
+    pub row_breaks: Vec<u16>,
+    /// Zero-based columns starting a new page
+    pub col_breaks: Vec<u16>,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup {
+            paper_size: None,
+            scale: 100,
+            landscape: false,
+            fit_to_page: false,
+            fit_width: 1,
+            fit_height: 1,
+            first_page_number: None,
+            margins: Margins::default(),
+            header: String::new(),
+            footer: String::new(),
+            center_horizontally: false,
+            center_vertically: false,
+            print_gridlines: false,
+            print_headings: false,
+            row_breaks: Vec::new(),
+            col_breaks: Vec::new(),
+        }
+    }
+}
+
+/// Page margins in inches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Margins {
+    pub left: f64,
+    pub right: f64,
+    pub top: f64,
+    pub bottom: f64,
+    pub header: f64,
+    pub footer: f64,
+}
+
+impl Default for Margins {
+    // Excel's defaults, used when the margin records are missing
+    fn default() -> Self {
+        Margins {
+            left: 0.75,
+            right: 0.75,
+            top: 1.0,
+            bottom: 1.0,
+            header: 0.5,
+            footer: 0.5,
+        }
+    }
+}
+
+/// Metadata of a sheet from its BoundSheet8 record.
+#[derive(Debug, Clone)]
+pub struct SheetInfo {
+    pub name: String,
+    pub state: SheetState,
+    pub r#type: SheetType,
+    /// Stream position of the sheet's BOF record
+    pub pos: u32,
+}
+
+/// Defined name from a LBL record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinedName {
+    /// Name of the defined name, built-in names have the `_xlnm.` prefix, e.g. `_xlnm.Print_Area`
+    pub name: String,
+    /// Sheet the name is local to, `None` for the workbook scope
+    pub sheet: Option<String>,
+    pub hidden: bool,
+    pub builtin: bool,
+    /// Formula text without the leading `=`
+    pub formula: String,
+}
+
+/// Named cell style, from a STYLE record and its STYLEEXT record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellStyle {
+    /// Name of the style, e.g. `Normal`, `Heading 1` or a user-defined name
+    pub name: String,
+    /// Index of the style XF record
+    pub xf: u16,
+    /// Identifier of a built-in style, e.g. 0 for `Normal`, as the `builtinId` of XLSX
+    pub builtin: Option<u8>,
+    /// Zero-based outline level of the `RowLevel_` and `ColLevel_` built-in styles
+    pub outline_level: Option<u8>,
+    /// Whether the style isn't shown in the style gallery
+    pub hidden: bool,
+    /// Group of the style gallery, from the STYLEEXT record
+    pub category: Option<StyleCategory>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleCategory {
+    Custom,
+    GoodBadNeutral,
+    DataModel,
+    TitleHeading,
+    ThemedCell,
+    NumberFormat,
+}
+
+impl From<styleext::Category> for StyleCategory {
+    fn from(category: styleext::Category) -> StyleCategory {
+        match category {
+            styleext::Category::Custom => StyleCategory::Custom,
+            styleext::Category::GoodBadNeutral => StyleCategory::GoodBadNeutral,
+            styleext::Category::DataModel => StyleCategory::DataModel,
+            styleext::Category::TitleHeading => StyleCategory::TitleHeading,
+            styleext::Category::ThemedCell => StyleCategory::ThemedCell,
+            styleext::Category::NumberFormat => StyleCategory::NumberFormat,
+        }
+    }
+}
+
+/// Cells of a row, in column order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    /// Zero-based row
+    pub row: u16,
+    pub cells: Vec<Cell>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cell {
+    /// Zero-based row
+    pub row: u16,
+    /// Zero-based column
+    pub col: u16,
+    pub value: CellValue,
+    /// Index of the cell's XF record
+    pub xf: u16,
+    // only boxed for the few shared strings with runs or phonetic text
+    pub(crate) text_format: Option<Box<TextFormat>>,
+}
+
+impl Cell {
+    /// Formatting runs of a shared string value.
+    pub fn runs(&self) -> &[TextRun] {
+        self.text_format
+            .as_ref()
+            .map_or(&[], |format| format.runs.as_slice())
+    }
+
+    /// Phonetic text of a shared string value, e.g. the furigana of Japanese text.
+    pub fn phonetic(&self) -> Option<&str> {
+        self.text_format.as_ref()?.phonetic.as_deref()
+    }
+}
+
+/// Formatting runs and phonetic text of a shared string.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TextFormat {
+    pub(crate) runs: Vec<TextRun>,
+    pub(crate) phonetic: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// No value, e.g. a BLANK cell only holding a format
+    Empty,
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Error(ErrorKind),
+    /// Serial number in the 1900 date system, the cell has a date or time format
+    DateTime(f64),
+    /// Formula text without the leading `=`, and its cached result
+    Formula {
+        text: String,
+        cached: Box<CellValue>,
+    },
+}
+
+/// Formula of a cell that couldn't be decompiled, e.g. with a token unknown to BIFF8.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaError {
+    /// Zero-based row
+    pub row: u16,
+    /// Zero-based column
+    pub col: u16,
+    pub message: String,
+}
+
+/// Note attached to a cell, from the NOTE record and the TXO record of its comment object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    /// Zero-based row
+    pub row: u16,
+    /// Zero-based column
+    pub col: u16,
+    pub author: String,
+    pub text: String,
+    /// Formatting runs of the text
+    pub runs: Vec<TextRun>,
+    /// Whether the note is always shown, not only when hovering the cell
+    pub visible: bool,
+}
+
+/// Hyperlink of a cell range, from the HLINK record and its HLINKTOOLTIP record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperlink {
+    /// Zero-based first row
+    pub row_first: u16,
+    /// Zero-based last row
+    pub row_last: u16,
+    /// Zero-based first column
+    pub col_first: u16,
+    /// Zero-based last column
+    pub col_last: u16,
+    /// URL, mailto address or file path, `None` for links within the workbook
+    pub target: Option<String>,
+    /// Location in the target, or in the workbook, e.g. `Sheet2!A1`
+    pub location: Option<String>,
+    pub display: Option<String>,
+    pub tooltip: Option<String>,
+}
+
+/// Rectangular range of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRange {
+    /// Zero-based first row
+    pub row_first: u16,
+    /// Zero-based last row
+    pub row_last: u16,
+    /// Zero-based first column
+    pub col_first: u16,
+    /// Zero-based last column
+    pub col_last: u16,
+}
+
+impl From<&Ref8> for CellRange {
+    fn from(ref8: &Ref8) -> Self {
+        CellRange {
+            row_first: ref8.row_min,
+            row_last: ref8.row_max,
+            col_first: ref8.col_min,
+            col_last: ref8.col_max,
+        }
+    }
+}
+
+/// Conditional formatting of cell ranges, from a CONDFMT or CONDFMT12 record and the CF or CF12 records following it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalFormat {
+    pub ranges: Vec<CellRange>,
+    pub rules: Vec<ConditionalRule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalRule {
+    pub condition: Condition,
+    /// Formatting of the cells meeting the condition
+    pub format: DifferentialFormat,
+    /// Evaluation order among the rules of the sheet, lowest first, `None` when not stored
+    pub priority: Option<u16>,
+    /// Whether the rules evaluated after this one are skipped when the condition is met
+    pub stop_if_true: bool,
+}
+
+/// Condition of a rule, with formulas relative to the top left cell of the ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Cell value compared with a formula, or with two for `Between` and `NotBetween`
+    CellIs {
+        operator: Operator,
+        formula1: String,
+        formula2: Option<String>,
+    },
+    /// Formula evaluating to true for the cells to format
+    Expression(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Between,
+    NotBetween,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl Operator {
+    pub(crate) fn from_cp(cp: u8) -> Option<Self> {
+        match cp {
+            1 => Some(Operator::Between),
+            2 => Some(Operator::NotBetween),
+            3 => Some(Operator::Equal),
+            4 => Some(Operator::NotEqual),
+            5 => Some(Operator::GreaterThan),
+            6 => Some(Operator::LessThan),
+            7 => Some(Operator::GreaterThanOrEqual),
+            8 => Some(Operator::LessThanOrEqual),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_typ(typ: u8) -> Option<Self> {
+        Self::from_cp(typ + 1)
+    }
+
+    pub(crate) fn from_grbit_sign(grbit_sign: u8) -> Option<Self> {
+        match grbit_sign {
+            1 => Some(Operator::LessThan),
+            2 => Some(Operator::Equal),
+            3 => Some(Operator::LessThanOrEqual),
+            4 => Some(Operator::GreaterThan),
+            5 => Some(Operator::NotEqual),
+            6 => Some(Operator::GreaterThanOrEqual),
+            _ => None,
+        }
+    }
+}
+
+/// Formatting applied over the cell format, `None` for the attributes left unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DifferentialFormat {
+    /// Built-in number format id
+    pub number_format_id: Option<u16>,
+    /// Custom number format code
+    pub number_format: Option<String>,
+    /// Font height in twips
+    pub font_height: Option<u16>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub strikeout: Option<bool>,
+    /// Underline style, with the values of the FONT record
+    pub underline: Option<u8>,
+    /// Palette index of the font color
+    pub font_color: Option<u16>,
+    /// Fill pattern, with the values of the XF record
+    pub fill_pattern: Option<u8>,
+    /// Palette index of the pattern color
+    pub fill_fore_color: Option<u16>,
+    /// Palette index of the fill color
+    pub fill_back_color: Option<u16>,
+    pub border_left: Option<BorderLine>,
+    pub border_right: Option<BorderLine>,
+    pub border_top: Option<BorderLine>,
+    pub border_bottom: Option<BorderLine>,
+}
+
+/// Data validation of cell ranges, from a DV record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataValidation {
+    pub ranges: Vec<CellRange>,
+    pub kind: ValidationKind,
+    /// Comparison of the value with the formulas, for the types other than `List` and `Custom`
+    pub operator: Operator,
+    /// First formula relative to the top left cell of the ranges, or a list as a quoted comma separated string
+    pub formula1: Option<String>,
+    /// Second formula, for `Between` and `NotBetween`
+    pub formula2: Option<String>,
+    pub allow_blank: bool,
+    /// Whether lists show a drop-down in the cell
+    pub show_dropdown: bool,
+    pub show_input_message: bool,
+    pub show_error_message: bool,
+    pub error_style: ErrorStyle,
+    pub prompt_title: String,
+    pub prompt: String,
+    pub error_title: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationKind {
+    Any,
+    Whole,
+    Decimal,
+    List,
+    Date,
+    Time,
+    TextLength,
+    Custom,
+}
+
+impl ValidationKind {
+    pub(crate) fn from_val_type(val_type: u8) -> Self {
+        match val_type {
+            1 => ValidationKind::Whole,
+            2 => ValidationKind::Decimal,
+            3 => ValidationKind::List,
+            4 => ValidationKind::Date,
+            5 => ValidationKind::Time,
+            6 => ValidationKind::TextLength,
+            7 => ValidationKind::Custom,
+            _ => ValidationKind::Any,
+        }
+    }
+}
+
+/// Auto filter of a worksheet, from its AUTOFILTERINFO and AUTOFILTER records and its `_FilterDatabase` name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoFilter {
+    /// Header row and rows of the filtered table
+    pub range: CellRange,
+    /// Columns with criteria
+    pub columns: Vec<FilterColumn>,
+    /// Whether rows are hidden by the criteria, from the FILTERMODE record
+    pub filtered: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterColumn {
+    /// Zero-based column offset in the range
+    pub col: u16,
+    pub criteria: FilterCriteria,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterCriteria {
+    /// Values selected in the list, blanks as an empty string
+    Values(Vec<String>),
+    /// One or two comparisons, joined with `and` or `or`, with `*` and `?` wildcards in the strings
+    Custom {
+        and: bool,
+        conditions: Vec<FilterCondition>,
+    },
+    /// Highest or lowest items, or percent of the items
+    Top10 {
+        top: bool,
+        percent: bool,
+        value: u16,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterCondition {
+    pub operator: Operator,
+    /// Value compared with the cells, empty for blanks
+    pub value: String,
+}
+
+/// Action on invalid input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorStyle {
+    /// The value is rejected
+    Stop,
+    /// The value is kept when confirmed
+    Warning,
+    /// The value is kept
+    Information,
+}
+
+impl ErrorStyle {
+    pub(crate) fn from_err_style(err_style: u8) -> Self {
+        match err_style {
+            1 => ErrorStyle::Warning,
+            2 => ErrorStyle::Information,
+            _ => ErrorStyle::Stop,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderLine {
+    /// Line style, with the values of the XF record
+    pub style: u8,
+    /// Palette index of the line color
+    pub color: u16,
+}
+
+/// Font applied from a character of a text until the next run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextRun {
+    /// Zero-based index of the first character
+    pub start: u16,
+    /// Index of the FONT record, as in XF records
+    pub font: u16,
+}
+
+// 2.5.10 Bes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Null,
+    Div0,
+    Value,
+    Ref,
+    Name,
+    Num,
+    NA,
+    GettingData,
+    /// Code of an error unknown to BIFF8, shown as `#N/A`
+    Unknown(u8),
+}
+
+impl ErrorKind {
+    pub(crate) fn from_code(code: u8) -> Self {
+        match code {
+            0x00 => ErrorKind::Null,
+            0x07 => ErrorKind::Div0,
+            0x0F => ErrorKind::Value,
+            0x17 => ErrorKind::Ref,
+            0x1D => ErrorKind::Name,
+            0x24 => ErrorKind::Num,
+            0x2A => ErrorKind::NA,
+            0x2B => ErrorKind::GettingData,
+            _ => ErrorKind::Unknown(code),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Null => "#NULL!",
+            ErrorKind::Div0 => "#DIV/0!",
+            ErrorKind::Value => "#VALUE!",
+            ErrorKind::Ref => "#REF!",
+            ErrorKind::Name => "#NAME?",
+            ErrorKind::Num => "#NUM!",
+            ErrorKind::NA | ErrorKind::Unknown(_) => "#N/A",
+            ErrorKind::GettingData => "#GETTING_DATA",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
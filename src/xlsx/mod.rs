@@ -0,0 +1,1338 @@
+use super::{
+    error::{Error, Result},
+    formula,
+    model::{Global, Shared},
+    record::{
+        RefU,
+        xf::{BorderStyle, Pattern},
+        xfext::{
+            ColorTheme, ColorValue, ExtProp, FontScheme, FullColorExt, GradientType, XFExtGradient,
+        },
+    },
+    workbook::{
+        BorderLine, Cell, CellRange, CellValue, Condition, DefinedName, DifferentialFormat,
+        Hyperlink, Note, Operator, PageSetup, Sheet, SheetView, TextRun, Workbook,
+    },
+};
+use modular_bitfield::Specifier;
+use std::{
+    fs::File,
+    io::{Cursor, Read, Seek, Write},
+    path::Path,
+};
+use umya_spreadsheet::{
+    Border, Break, CellFormula, CellFormulaValues, Color, Comment, ConditionalFormatValues,
+    ConditionalFormatting, ConditionalFormattingRule, Coordinate,
+    DataValidation as XDataValidation, DataValidations, Fill, Font, Formula, GradientStop,
+    Hyperlink as XHyperlink, OddFooter, OddHeader, OrientationValues, Pane as XPane,
+    PaneStateValues, PaneValues, RichText, Selection, SequenceOfReferences,
+    SheetView as XSheetView, SheetViewValues, Spreadsheet, Style, TextElement, Worksheet,
+    drawing::{Color2Type, ColorScheme, RgbColorModelHex},
+    helper::coordinate::coordinate_from_index,
+    new_file_empty_worksheet,
+    vml::spreadsheet::Visible,
+    writer::xlsx,
+};
+
+mod patch;
+
+/// Converts an XLS file to XLSX format.
+///
+/// # Arguments
+///
+/// * `path` - Path to the XLS file to convert
+///
+/// # Returns
+///
+/// * `Result<impl AsRef<Path>>` - Path to the converted XLSX file path on success, or an error on failure
+pub fn xls2xlsx(path: impl AsRef<Path>) -> Result<impl AsRef<Path>> {
+    if path.as_ref().extension() != Some("xls".as_ref()) {
+        return Err(Error::XlsExt);
+    }
+
+    let workbook = Workbook::open(&path)?;
+    let xpath = path.as_ref().with_extension("xlsx");
+    workbook.save_xlsx(&xpath)?;
+
+    Ok(xpath)
+}
+
+/// Converts XLS content from any reader to XLSX format, writing the result into `writer`.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+/// * `writer` - Destination of the XLSX content
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok(()) on success, or an error on failure
+pub fn xls2xlsx_from_reader<R: Read + Seek, W: Write + Seek>(reader: R, writer: W) -> Result<()> {
+    Workbook::open_from_reader(reader)?.save_xlsx_to_writer(writer)
+}
+
+/// Converts in-memory XLS bytes to XLSX format, writing the result into `writer`.
+///
+/// # Arguments
+///
+/// * `bytes` - Content of the XLS file
+/// * `writer` - Destination of the XLSX content
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok(()) on success, or an error on failure
+pub fn xls2xlsx_from_bytes<W: Write + Seek>(bytes: &[u8], writer: W) -> Result<()> {
+    xls2xlsx_from_reader(Cursor::new(bytes), writer)
+}
+
+/// Saves a umya_spreadsheet::Spreadsheet structure to an XLSX file.
+///
+/// Only what umya-spreadsheet models is written, `Workbook::save_xlsx` also writes the rest, e.g. the filter criteria.
+///
+/// # Arguments
+///
+/// * `workbook` - umya_spreadsheet::Spreadsheet structure to save
+/// * `path` - Path to save the XLSX file path to
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok(()) on success, or an error on failure
+pub fn save(workbook: &Spreadsheet, path: impl AsRef<Path>) -> Result<()> {
+    xlsx::write(workbook, path)?;
+    Ok(())
+}
+
+/// Saves a umya_spreadsheet::Spreadsheet structure as XLSX content into any writer.
+///
+/// Only what umya-spreadsheet models is written, `Workbook::save_xlsx` also writes the rest, e.g. the filter criteria.
+///
+/// # Arguments
+///
+/// * `workbook` - umya_spreadsheet::Spreadsheet structure to save
+/// * `writer` - Destination of the XLSX content
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok(()) on success, or an error on failure
+pub fn save_to_writer<W: Write + Seek>(workbook: &Spreadsheet, writer: W) -> Result<()> {
+    xlsx::write_writer(workbook, writer)?;
+    Ok(())
+}
+
+/// Opens and reads an XLS file, returning its contents as a umya_spreadsheet::Spreadsheet structure.
+///
+/// # Arguments
+///
+/// * `path` - Path to the XLS file to open
+///
+/// # Returns
+///
+/// * `Result<umya_spreadsheet::Spreadsheet>` - umya_spreadsheet::Spreadsheet structure containing the file contents on success, or an error on failure
+pub fn open(path: impl AsRef<Path>) -> Result<Spreadsheet> {
+    Workbook::open(path)?.to_spreadsheet()
+}
+
+/// Opens and reads an encrypted XLS file, returning its contents as a umya_spreadsheet::Spreadsheet structure.
+///
+/// The default password `VelvetSweatshop` is tried when `password` doesn't match.
+///
+/// # Arguments
+///
+/// * `path` - Path to the XLS file to open
+/// * `password` - Password to open the workbook
+///
+/// # Returns
+///
+/// * `Result<umya_spreadsheet::Spreadsheet>` - umya_spreadsheet::Spreadsheet structure containing the file contents on success, or an error on failure
+pub fn open_with_password(path: impl AsRef<Path>, password: &str) -> Result<Spreadsheet> {
+    Workbook::open_with_password(path, password)?.to_spreadsheet()
+}
+
+/// Reads in-memory XLS bytes, returning its contents as a umya_spreadsheet::Spreadsheet structure.
+///
+/// # Arguments
+///
+/// * `bytes` - Content of the XLS file
+///
+/// # Returns
+///
+/// * `Result<umya_spreadsheet::Spreadsheet>` - umya_spreadsheet::Spreadsheet structure containing the file contents on success, or an error on failure
+pub fn open_from_bytes(bytes: &[u8]) -> Result<Spreadsheet> {
+    Workbook::open_from_bytes(bytes)?.to_spreadsheet()
+}
+
+/// Reads XLS content from any reader, returning its contents as a umya_spreadsheet::Spreadsheet structure.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the XLS content, e.g. a `File` or a `Cursor<Vec<u8>>`
+///
+/// # Returns
+///
+/// * `Result<umya_spreadsheet::Spreadsheet>` - umya_spreadsheet::Spreadsheet structure containing the file contents on success, or an error on failure
+pub fn open_from_reader<R: Read + Seek>(reader: R) -> Result<Spreadsheet> {
+    Workbook::open_from_reader(reader)?.to_spreadsheet()
+}
+
+impl Workbook {
+    /// Converts the workbook into a umya_spreadsheet::Spreadsheet structure, with values, formulas and formatting.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<umya_spreadsheet::Spreadsheet>` - umya_spreadsheet::Spreadsheet structure on success, or an error on failure
+    pub fn to_spreadsheet(&self) -> Result<Spreadsheet> {
+        let global = &self.global;
+        let mut spreadsheet = new_file_empty_worksheet();
+        if let Some(content) = &global.theme {
+            handle_theme(&mut spreadsheet, content);
+        }
+
+        for sheet in &self.sheets {
+            let worksheet = spreadsheet.new_sheet(&sheet.name).map_err(Error::msg)?;
+            worksheet.set_state(sheet.state.into());
+            handle_view(worksheet, sheet.view());
+
+            let format_properties = worksheet.get_sheet_format_properties_mut();
+            if let Some(height) = sheet.default_row_height() {
+                format_properties.set_default_row_height(height as f64 / 20.);
+            }
+            if let Some(width) = sheet.default_col_width() {
+                format_properties.set_base_column_width(width.into());
+            }
+            handle_page_setup(worksheet, sheet.page_setup());
+
+            for data in &sheet.colinfos {
+                for c in data.col_min..=data.col_max {
+                    let col = worksheet.get_column_dimension_by_number_mut(&(c + 1).into());
+
+                    col.set_hidden(data.info.hidden())
+                        .set_best_fit(data.info.best_fit());
+                    if data.info.user_set() {
+                        col.set_width(data.width as f64 / 256.);
+                    }
+                    // 15 is the default cell XF
+                    if data.ixfe != 15 {
+                        handle_style(col.get_style_mut(), global, data.ixfe.into());
+                    }
+                }
+            }
+
+            for data in &sheet.rowinfos {
+                let row = worksheet.get_row_dimension_mut(&(data.row + 1).into());
+                if data.info.hidden() {
+                    row.set_hidden(true);
+                } else {
+                    // issure with `set_height` not working
+                    row.set_height(data.height as f64 / 20.);
+                }
+                row.set_thick_bot(data.info.top_bdr());
+                if data.info.formatted() {
+                    handle_style(row.get_style_mut(), global, data.info.ixfe().into());
+                }
+            }
+
+            for cell in sheet.cells() {
+                handle_cell(worksheet, global, sheet, cell);
+            }
+
+            for ref8 in &sheet.merges {
+                worksheet.add_merge_cells(format!(
+                    "{}:{}",
+                    coordinate_from_index(&(ref8.col_min + 1).into(), &(ref8.row_min + 1).into()),
+                    coordinate_from_index(&(ref8.col_max + 1).into(), &(ref8.row_max + 1).into())
+                ));
+            }
+
+            for note in sheet.notes() {
+                worksheet.add_comments(handle_note(global, note));
+            }
+
+            for hyperlink in sheet.hyperlinks() {
+                handle_hyperlink(worksheet, hyperlink);
+            }
+
+            handle_conditional_formats(worksheet, global, sheet);
+            handle_data_validations(worksheet, sheet);
+
+            // the levels of each row and column and the summary placement are patched in by `save_xlsx`
+            let outline = sheet.outline();
+            let format_properties = worksheet.get_sheet_format_properties_mut();
+            if outline.row_level > 0 {
+                format_properties.set_outline_level_row(outline.row_level);
+            }
+            if outline.col_level > 0 {
+                format_properties.set_outline_level_column(outline.col_level);
+            }
+
+            // the filter columns are patched in by `save_xlsx`
+            if let Some(auto_filter) = sheet.auto_filter() {
+                worksheet.set_auto_filter(cell_range(&auto_filter.range));
+            }
+        }
+
+        for defined_name in &self.defined_names {
+            handle_defined_name(&mut spreadsheet, defined_name);
+        }
+
+        if let Some(active) = self.active {
+            spreadsheet.set_active_sheet(active as u32);
+        }
+
+        Ok(spreadsheet)
+    }
+
+    /// Saves the workbook as an XLSX file, with what umya-spreadsheet doesn't model, e.g. the filter criteria and outlines.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to save the XLSX file path to
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Ok(()) on success, or an error on failure
+    pub fn save_xlsx(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.save_xlsx_to_writer(File::create(path)?)
+    }
+
+    /// Saves the workbook as XLSX content into any writer, with what umya-spreadsheet doesn't model.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the XLSX content
+    ///
+    /// # Returns
+    ///
+    /// * `Result<()>` - Ok(()) on success, or an error on failure
+    pub fn save_xlsx_to_writer<W: Write + Seek>(&self, writer: W) -> Result<()> {
+        patch::write(self, self.to_spreadsheet()?, writer)
+    }
+}
+
+// umya-spreadsheet has no attributes for the headings, zeros and right-to-left flags
+fn handle_view(worksheet: &mut Worksheet, view: &SheetView) {
+    let mut xview = XSheetView::default();
+    xview
+        .set_workbook_view_id(0)
+        .set_tab_selected(view.selected)
+        .set_show_grid_lines(view.show_gridlines)
+        .set_zoom_scale(view.zoom.into())
+        .set_zoom_scale_normal(view.zoom.into());
+    if view.page_break_preview {
+        xview.set_view(SheetViewValues::PageBreakPreview);
+    }
+    if (view.top_row, view.left_col) != (0, 0) {
+        xview.set_top_left_cell(coordinate_from_index(
+            &(view.left_col as u32 + 1),
+            &(view.top_row as u32 + 1),
+        ));
+    }
+
+    let mut active_pane = PaneValues::TopLeft;
+    if let Some(pane) = &view.pane {
+        active_pane = pane.active.into();
+        let mut xpane = XPane::default();
+        let mut top_left_cell = Coordinate::default();
+        top_left_cell
+            .set_col_num(pane.left_col as u32 + 1)
+            .set_row_num(pane.top_row as u32 + 1);
+        xpane
+            .set_top_left_cell(top_left_cell)
+            .set_active_pane(pane.active.into())
+            .set_state(if pane.frozen {
+                PaneStateValues::Frozen
+            } else {
+                PaneStateValues::Split
+            });
+        // frozen panes count rows and columns, split panes twips
+        if pane.x > 0 {
+            xpane.set_horizontal_split(pane.x.into());
+        }
+        if pane.y > 0 {
+            xpane.set_vertical_split(pane.y.into());
+        }
+        xview.set_pane(xpane);
+    }
+
+    let (row, col) = view.active_cell;
+    let mut active_cell = Coordinate::default();
+    active_cell
+        .set_col_num(col as u32 + 1)
+        .set_row_num(row as u32 + 1);
+    let mut sqref = SequenceOfReferences::default();
+    sqref.set_sqref(active_cell.get_coordinate());
+    let mut selection = Selection::default();
+    selection
+        .set_pane(active_pane)
+        .set_active_cell(active_cell)
+        .set_sequence_of_references(sqref);
+    xview.set_selection(selection);
+
+    worksheet
+        .get_sheet_views_mut()
+        .add_sheet_view_list_mut(xview);
+}
+
+// umya-spreadsheet has no attributes for the fit to page flag, first page number and printed
+// gridlines and headings, so fitting is written as fitToWidth/fitToHeight instead of a scale
+fn handle_page_setup(worksheet: &mut Worksheet, page_setup: &PageSetup) {
+    let xpage_setup = worksheet.get_page_setup_mut();
+    if let Some(paper_size) = page_setup.paper_size {
+        xpage_setup.set_paper_size(paper_size.into());
+    }
+    if page_setup.fit_to_page {
+        xpage_setup
+            .set_fit_to_width(page_setup.fit_width.into())
+            .set_fit_to_height(page_setup.fit_height.into());
+    } else if page_setup.scale != 100 {
+        xpage_setup.set_scale(page_setup.scale.into());
+    }
+    if page_setup.landscape {
+        xpage_setup.set_orientation(OrientationValues::Landscape);
+    }
+
+    let margins = &page_setup.margins;
+    worksheet
+        .get_page_margins_mut()
+        .set_left(margins.left)
+        .set_right(margins.right)
+        .set_top(margins.top)
+        .set_bottom(margins.bottom)
+        .set_header(margins.header)
+        .set_footer(margins.footer);
+
+    let header_footer = worksheet.get_header_footer_mut();
+    if !page_setup.header.is_empty() {
+        let mut header = OddHeader::default();
+        header.set_value(&page_setup.header);
+        header_footer.set_odd_header(header);
+    }
+    if !page_setup.footer.is_empty() {
+        let mut footer = OddFooter::default();
+        footer.set_value(&page_setup.footer);
+        header_footer.set_odd_footer(footer);
+    }
+
+    worksheet
+        .get_print_options_mut()
+        .set_horizontal_centered(page_setup.center_horizontally)
+        .set_vertical_centered(page_setup.center_vertically);
+
+    // breaks span the whole sheet, up to the last XLSX column or row
+    for &row in &page_setup.row_breaks {
+        let mut brk = Break::default();
+        brk.set_id(row.into())
+            .set_max(16383)
+            .set_manual_page_break(true);
+        worksheet.get_row_breaks_mut().add_break_list(brk);
+    }
+    for &col in &page_setup.col_breaks {
+        let mut brk = Break::default();
+        brk.set_id(col.into())
+            .set_max(1048575)
+            .set_manual_page_break(true);
+        worksheet.get_column_breaks_mut().add_break_list(brk);
+    }
+}
+
+fn handle_defined_name(spreadsheet: &mut Spreadsheet, defined_name: &DefinedName) {
+    // the sheet index in the XLSX only counts worksheets
+    let local = match &defined_name.sheet {
+        Some(name) => match spreadsheet
+            .get_sheet_collection()
+            .iter()
+            .position(|worksheet| worksheet.get_name() == name)
+        {
+            Some(index) => Some(index),
+            None => return,
+        },
+        None => None,
+    };
+    let Some(worksheet) = spreadsheet.get_sheet_mut(&local.unwrap_or(0)) else {
+        return;
+    };
+
+    // umya-spreadsheet splits the formula on its commas and keeps only the last part that isn't
+    // a cell reference, so the areas of a union, e.g. the rows and columns of Print_Titles, are
+    // added one by one, and any other formula is kept whole by putting it in parentheses
+    let areas = split_union(&defined_name.formula);
+    let union = areas.iter().all(|area| is_area(area));
+    let formula = if union {
+        ""
+    } else if defined_name.formula.contains([',', '"']) {
+        &format!("({})", defined_name.formula)
+    } else {
+        &defined_name.formula
+    };
+    if worksheet
+        .add_defined_name(defined_name.name.as_str(), formula)
+        .is_err()
+    {
+        return;
+    }
+    let Some(mut xdefined_name) = worksheet.get_defined_names_mut().pop() else {
+        return;
+    };
+    if union {
+        for area in areas {
+            xdefined_name.add_address(area);
+        }
+    }
+    if defined_name.hidden {
+        xdefined_name.set_hidden(true);
+    }
+
+    match local {
+        Some(index) => {
+            xdefined_name.set_local_sheet_id(index as u32);
+            worksheet.add_defined_names(xdefined_name);
+        }
+        None => spreadsheet.add_defined_names(xdefined_name),
+    }
+}
+
+/// Splits a formula on the commas of its union operators, outside of quotes and parentheses.
+fn split_union(formula: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut depth, mut quote) = (0, 0, None);
+    for (i, c) in formula.char_indices() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (_, Some(_)) => (),
+            ('(', None) => depth += 1,
+            (')', None) => depth -= 1,
+            (',', None) if depth == 0 => {
+                parts.push(&formula[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(&formula[start..]);
+    parts
+}
+
+/// Whether the formula is a single area, e.g. `Sheet1!$A$1:$B$2`, `Sheet1!$A:$B` or `Sheet1!$1:$2`.
+fn is_area(formula: &str) -> bool {
+    let range = formula.rsplit_once('!').map_or(formula, |(_, range)| range);
+    let parts = range
+        .split(':')
+        .map(|part| {
+            let part = part.strip_prefix('$').unwrap_or(part);
+            let letters = part.len()
+                - part
+                    .trim_start_matches(|c: char| c.is_ascii_uppercase())
+                    .len();
+            let (col, row) = part.split_at(letters);
+            let row = row.strip_prefix('$').unwrap_or(row);
+            let valid = letters <= 3 && row.bytes().all(|b| b.is_ascii_digit());
+            (valid, !col.is_empty(), !row.is_empty())
+        })
+        .collect::<Vec<_>>();
+    match parts[..] {
+        [(true, true, true)] => true,
+        // whole columns or whole rows need both ends
+        [(true, c1, r1), (true, c2, r2)] => (c1, r1) == (c2, r2) && (c1 || r1),
+        _ => false,
+    }
+}
+
+fn handle_cell(worksheet: &mut Worksheet, global: &Global, sheet: &Sheet, cell: &Cell) {
+    let xcell = worksheet.get_cell_mut((cell.col as u32 + 1, cell.row as u32 + 1));
+    handle_style(xcell.get_style_mut(), global, cell.xf.into());
+
+    let value = match &cell.value {
+        CellValue::Formula { cached, .. } => cached.as_ref(),
+        value => value,
+    };
+    match value {
+        CellValue::String(s) if !cell.runs().is_empty() => {
+            xcell.set_rich_text(handle_rich_text(global, s, cell.runs()));
+        }
+        CellValue::String(s) => {
+            xcell.set_value_string(s);
+        }
+        CellValue::Number(n) | CellValue::DateTime(n) => {
+            xcell.set_value_number(*n);
+        }
+        CellValue::Bool(b) => {
+            xcell.set_value_bool(*b);
+        }
+        CellValue::Error(e) => {
+            xcell.set_error(e.as_str());
+        }
+        CellValue::Empty | CellValue::Formula { .. } => (),
+    }
+
+    // the formula is set after the value, as setting a value clears the formula
+    if let CellValue::Formula { text, .. } = &cell.value {
+        let formula = handle_formula(sheet, cell, text);
+        xcell.get_cell_value_mut().set_formula_obj(formula);
+    }
+}
+
+fn handle_note(global: &Global, note: &Note) -> Comment {
+    let mut comment = Comment::default();
+    comment
+        .new_comment((note.col as u32 + 1, note.row as u32 + 1))
+        .set_author(&note.author);
+
+    if note.runs.is_empty() {
+        comment.set_text_string(&note.text);
+    } else {
+        comment
+            .get_text_mut()
+            .set_rich_text(handle_rich_text(global, &note.text, &note.runs));
+    }
+
+    if note.visible {
+        let shape = comment.get_shape_mut();
+        let style = shape
+            .get_style()
+            .replace("visibility:hidden", "visibility:visible");
+        shape.set_style(style);
+        let mut visible = Visible::default();
+        visible.set_value(true);
+        shape.get_client_data_mut().set_visible(visible);
+    }
+
+    comment
+}
+
+fn handle_rich_text(global: &Global, text: &str, runs: &[TextRun]) -> RichText {
+    let chars = text.encode_utf16().collect::<Vec<_>>();
+    let mut rich_text = RichText::default();
+    // the text before the first run keeps the default font
+    if let Some(first) = runs.first()
+        && first.start > 0
+    {
+        let first = (first.start as usize).min(chars.len());
+        let mut element = TextElement::default();
+        element.set_text(String::from_utf16_lossy(&chars[..first]));
+        rich_text.add_rich_text_elements(element);
+    }
+    for (i, run) in runs.iter().enumerate() {
+        let start = (run.start as usize).min(chars.len());
+        let end = runs
+            .get(i + 1)
+            .map_or(chars.len(), |next| (next.start as usize).min(chars.len()));
+        let mut element = TextElement::default();
+        element.set_text(String::from_utf16_lossy(&chars[start..end.max(start)]));
+        handle_font(element.get_font_mut(), global, run.font);
+        rich_text.add_rich_text_elements(element);
+    }
+    rich_text
+}
+
+fn handle_hyperlink(worksheet: &mut Worksheet, hyperlink: &Hyperlink) {
+    let mut xhyperlink = XHyperlink::default();
+    match (&hyperlink.target, &hyperlink.location) {
+        (Some(target), Some(location)) => xhyperlink.set_url(format!("{target}#{location}")),
+        (Some(target), None) => xhyperlink.set_url(target),
+        (None, Some(location)) => xhyperlink.set_url(location).set_location(true),
+        (None, None) => return,
+    };
+    if let Some(tooltip) = &hyperlink.tooltip {
+        xhyperlink.set_tooltip(tooltip);
+    }
+
+    // hyperlinks are attached to every cell of the range
+    for row in hyperlink.row_first..=hyperlink.row_last {
+        for col in hyperlink.col_first..=hyperlink.col_last {
+            worksheet
+                .get_cell_mut((col as u32 + 1, row as u32 + 1))
+                .set_hyperlink(xhyperlink.clone());
+        }
+    }
+}
+
+fn handle_conditional_formats(worksheet: &mut Worksheet, global: &Global, sheet: &Sheet) {
+    let mut position = 0;
+    for format in sheet.conditional_formats() {
+        let sqref: Vec<String> = format.ranges.iter().map(cell_range).collect();
+        // formulas are relative to the top left cell of the ranges
+        let row = format.ranges.iter().map(|r| r.row_first).min().unwrap_or(0);
+        let col = format.ranges.iter().map(|r| r.col_first).min().unwrap_or(0);
+        let cell = formula::cell(row, col, false, false);
+
+        let mut xformat = ConditionalFormatting::default();
+        xformat
+            .get_sequence_of_references_mut()
+            .set_sqref(sqref.join(" "));
+
+        for rule in &format.rules {
+            let mut xrule = ConditionalFormattingRule::default();
+            let text = match &rule.condition {
+                Condition::CellIs {
+                    operator,
+                    formula1,
+                    formula2: Some(formula2),
+                } => {
+                    // a rule holds a single formula, the range is checked by an expression
+                    xrule.set_type(ConditionalFormatValues::Expression);
+                    match operator {
+                        Operator::NotBetween => {
+                            format!("OR({cell}<{formula1},{cell}>{formula2})")
+                        }
+                        _ => format!("AND({cell}>={formula1},{cell}<={formula2})"),
+                    }
+                }
+                Condition::CellIs {
+                    operator, formula1, ..
+                } => {
+                    xrule
+                        .set_type(ConditionalFormatValues::CellIs)
+                        .set_operator((*operator).into());
+                    formula1.clone()
+                }
+                Condition::Expression(text) => {
+                    xrule.set_type(ConditionalFormatValues::Expression);
+                    text.clone()
+                }
+            };
+            let mut xformula = Formula::default();
+            xformula.set_string_value(text);
+
+            // rules without a stored priority are evaluated in order
+            position += 1;
+            xrule
+                .set_formula(xformula)
+                .set_priority(rule.priority.map_or(position, i32::from))
+                .set_style(handle_differential_format(global, &rule.format));
+            if rule.stop_if_true {
+                xrule.set_stop_if_true(true);
+            }
+            xformat.add_conditional_collection(xrule);
+        }
+
+        worksheet.add_conditional_formatting_collection(xformat);
+    }
+}
+
+// umya-spreadsheet has no attributes for the error style and drop-down flags
+fn handle_data_validations(worksheet: &mut Worksheet, sheet: &Sheet) {
+    if sheet.data_validations().is_empty() {
+        return;
+    }
+
+    let mut xvalidations = DataValidations::default();
+    for validation in sheet.data_validations() {
+        let sqref: Vec<String> = validation.ranges.iter().map(cell_range).collect();
+
+        let mut xvalidation = XDataValidation::default();
+        xvalidation
+            .set_type(validation.kind.into())
+            .set_operator(validation.operator.into())
+            .set_allow_blank(validation.allow_blank)
+            .set_show_input_message(validation.show_input_message)
+            .set_show_error_message(validation.show_error_message);
+        if !validation.prompt_title.is_empty() {
+            xvalidation.set_prompt_title(&validation.prompt_title);
+        }
+        if !validation.prompt.is_empty() {
+            xvalidation.set_prompt(&validation.prompt);
+        }
+        if !validation.error_title.is_empty() {
+            xvalidation.set_error_title(&validation.error_title);
+        }
+        if !validation.error.is_empty() {
+            xvalidation.set_error_message(&validation.error);
+        }
+        xvalidation
+            .get_sequence_of_references_mut()
+            .set_sqref(sqref.join(" "));
+        if let Some(formula1) = &validation.formula1 {
+            xvalidation.set_formula1(formula1);
+        }
+        if let Some(formula2) = &validation.formula2 {
+            xvalidation.set_formula2(formula2);
+        }
+        xvalidations.add_data_validation_list(xvalidation);
+    }
+
+    worksheet.set_data_validations(xvalidations);
+}
+
+// umya-spreadsheet has no number formats in differential formats
+fn handle_differential_format(global: &Global, format: &DifferentialFormat) -> Style {
+    let mut xstyle = Style::default();
+
+    let mut xfont = Font::default();
+    if let Some(height) = format.font_height {
+        xfont.set_size(height as f64 / 20.0);
+    }
+    if let Some(bold) = format.bold {
+        xfont.set_bold(bold);
+    }
+    if let Some(italic) = format.italic {
+        xfont.set_italic(italic);
+    }
+    if let Some(strikeout) = format.strikeout {
+        xfont.set_strikethrough(strikeout);
+    }
+    if let Some(underline) = format.underline {
+        xfont.set_underline(match underline {
+            0x01 => "single",
+            0x02 => "double",
+            0x21 => "singleAccounting",
+            0x22 => "doubleAccounting",
+            _ => "none",
+        });
+    }
+    if let Some(icv) = format.font_color {
+        handle_color(xfont.get_color_mut(), &global.palette, icv);
+    }
+    if xfont != Font::default() {
+        xstyle.set_font(xfont);
+    }
+
+    if format.fill_pattern.is_some()
+        || format.fill_fore_color.is_some()
+        || format.fill_back_color.is_some()
+    {
+        let mut xfill = Fill::default();
+        let fill_pattern = xfill.get_pattern_fill_mut();
+        if let Some(pattern) = format
+            .fill_pattern
+            .and_then(|fls| Pattern::from_bytes(fls).ok())
+        {
+            fill_pattern.set_pattern_type(pattern.into());
+        }
+        if let Some(icv) = format.fill_fore_color {
+            handle_color(
+                fill_pattern.get_foreground_color_mut(),
+                &global.palette,
+                icv,
+            );
+        }
+        if let Some(icv) = format.fill_back_color {
+            handle_color(
+                fill_pattern.get_background_color_mut(),
+                &global.palette,
+                icv,
+            );
+        }
+        xstyle.set_fill(xfill);
+    }
+
+    let lines = [
+        format.border_left,
+        format.border_right,
+        format.border_top,
+        format.border_bottom,
+    ];
+    if lines.iter().any(Option::is_some) {
+        let border = |line: &Option<BorderLine>| {
+            let mut xborder = Border::default();
+            if let Some(line) = line
+                && let Ok(style) = BorderStyle::from_bytes(line.style)
+            {
+                xborder.set_border_style(style.to_string());
+                handle_color(xborder.get_color_mut(), &global.palette, line.color);
+            }
+            xborder
+        };
+        let xborders = xstyle.get_borders_mut();
+        xborders.set_left_border(border(&format.border_left));
+        xborders.set_right_border(border(&format.border_right));
+        xborders.set_top_border(border(&format.border_top));
+        xborders.set_bottom_border(border(&format.border_bottom));
+    }
+
+    xstyle
+}
+
+fn handle_formula(sheet: &Sheet, cell: &Cell, text: &str) -> CellFormula {
+    let mut cell_formula = CellFormula::default();
+
+    match sheet.shared.get(&(cell.row, cell.col)) {
+        Some(Shared::Array(data)) => {
+            cell_formula.set_text(text);
+            cell_formula.set_formula_type(CellFormulaValues::Array);
+            cell_formula.set_reference(range(&data.r#ref));
+        }
+        Some(Shared::Table(data)) => {
+            cell_formula.set_formula_type(CellFormulaValues::DataTable);
+            cell_formula
+                .set_reference(range(&data.r#ref))
+                .set_data_table_2d(data.two_inputs)
+                .set_data_table_row(data.row_input)
+                .set_input_1deleted(data.deleted1)
+                .set_r1(formula::cell(
+                    data.row_input1,
+                    data.col_input1,
+                    false,
+                    false,
+                ));
+            if data.two_inputs {
+                cell_formula
+                    .set_input_2deleted(data.deleted2)
+                    .set_r2(formula::cell(
+                        data.row_input2,
+                        data.col_input2,
+                        false,
+                        false,
+                    ));
+            }
+        }
+        _ => {
+            cell_formula.set_text(text);
+        }
+    }
+
+    cell_formula
+}
+
+fn range(r#ref: &RefU) -> String {
+    format!(
+        "{}:{}",
+        formula::cell(r#ref.row_first, r#ref.col_first.into(), false, false),
+        formula::cell(r#ref.row_last, r#ref.col_last.into(), false, false)
+    )
+}
+
+fn cell_range(range: &CellRange) -> String {
+    format!(
+        "{}:{}",
+        formula::cell(range.row_first, range.col_first, false, false),
+        formula::cell(range.row_last, range.col_last, false, false)
+    )
+}
+
+/// Replaces the colors of the default theme with those of the Theme record.
+fn handle_theme(spreadsheet: &mut Spreadsheet, content: &[u8]) {
+    let Some(xml) = theme_xml(content) else {
+        return;
+    };
+    let Some(clr_scheme) = xml
+        .split_once("<a:clrScheme")
+        .and_then(|(_, rest)| rest.split_once("</a:clrScheme>"))
+        .map(|(clr_scheme, _)| clr_scheme)
+    else {
+        return;
+    };
+
+    let color_scheme = spreadsheet
+        .get_theme_mut()
+        .get_theme_elements_mut()
+        .get_color_scheme_mut();
+    type Setter = fn(&mut ColorScheme, Color2Type);
+    let setters: [(&str, Setter); 12] = [
+        ("dk1", ColorScheme::set_dk1),
+        ("lt1", ColorScheme::set_lt1),
+        ("dk2", ColorScheme::set_dk2),
+        ("lt2", ColorScheme::set_lt2),
+        ("accent1", ColorScheme::set_accent1),
+        ("accent2", ColorScheme::set_accent2),
+        ("accent3", ColorScheme::set_accent3),
+        ("accent4", ColorScheme::set_accent4),
+        ("accent5", ColorScheme::set_accent5),
+        ("accent6", ColorScheme::set_accent6),
+        ("hlink", ColorScheme::set_hlink),
+        ("folHlink", ColorScheme::set_fol_hlink),
+    ];
+    for (name, set) in setters {
+        let Some(rgb) = scheme_color(clr_scheme, name) else {
+            continue;
+        };
+        let mut hex = RgbColorModelHex::default();
+        hex.set_val(rgb);
+        let mut color = Color2Type::default();
+        color.set_rgb_color_model_hex(hex);
+        set(color_scheme, color);
+    }
+}
+
+/// Theme part of the Office Open XML package of the Theme record.
+fn theme_xml(content: &[u8]) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(content)).ok()?;
+    let name = archive
+        .file_names()
+        .find(|name| name.starts_with("theme/theme/") && name.ends_with(".xml"))?
+        .to_string();
+    let mut xml = String::new();
+    archive.by_name(&name).ok()?.read_to_string(&mut xml).ok()?;
+    Some(xml)
+}
+
+/// RGB of a color of the color scheme, from its `srgbClr`, or the last color of its `sysClr`.
+fn scheme_color<'a>(clr_scheme: &'a str, name: &str) -> Option<&'a str> {
+    let (_, element) = clr_scheme.split_once(&format!("<a:{name}>"))?;
+    let (element, _) = element.split_once(&format!("</a:{name}>"))?;
+    let attribute = |key: &str| {
+        let (_, value) = element.split_once(key)?;
+        value.split('"').next()
+    };
+    attribute("lastClr=\"").or_else(|| attribute("val=\""))
+}
+
+fn handle_color(color: &mut Color, palette: &Option<Vec<String>>, icv: u16) {
+    if icv == 0x7FFF {
+        return;
+    }
+    if let Some(palette) = palette
+        && let Some(argb) = palette.get(icv as usize)
+    {
+        color.set_argb(argb);
+    } else {
+        color.set_indexed(icv as u32);
+    }
+}
+
+fn handle_font(xfont: &mut Font, global: &Global, ifnt: u16) {
+    if let Some(sfont) = global.font(ifnt) {
+        xfont
+            .set_size((sfont.height as f64) / 20.0)
+            .set_name(&sfont.name)
+            .set_family(sfont.family.into())
+            .set_charset(sfont.charset.into())
+            .set_bold(sfont.bold)
+            .set_italic(sfont.info.italic())
+            .set_strikethrough(sfont.info.strike_out())
+            .set_underline(sfont.underline.to_string());
+        handle_color(xfont.get_color_mut(), &global.palette, sfont.icv);
+    }
+}
+
+// umya-spreadsheet keeps its stylesheet private, the named style of the XF, `Global::style_xf`,
+// is patched in by `save_xlsx`
+fn handle_style(xstyle: &mut Style, global: &Global, ixfe: usize) {
+    if let Some(xf) = global.xfs.get(ixfe) {
+        // number format
+        if matches!(xf.ifmt, 1..=4 | 9..=22 | 27..=40 | 45..=62 | 67..=81) {
+            xstyle
+                .get_number_format_mut()
+                .set_number_format_id(xf.ifmt.into());
+        } else if let Some(format) = global.formats.get(&xf.ifmt.into()) {
+            xstyle.get_number_format_mut().set_format_code(format);
+        }
+
+        // font
+        handle_font(xstyle.get_font_mut(), global, xf.ifnt);
+
+        // protection
+        let protection = xstyle.get_protection_mut();
+        protection.set_locked(xf.protection.locked());
+        protection.set_hidden(xf.protection.hidden());
+
+        // alignment
+        let alignment = xstyle.get_alignment_mut();
+        alignment.set_horizontal(xf.alignment.horiz_align().into());
+        alignment.set_vertical(xf.alignment.vert_align().into());
+        alignment.set_wrap_text(xf.alignment.warp_text());
+        alignment.set_text_rotation(xf.alignment.text_rotation().into());
+
+        // borders
+        let borders = xstyle.get_borders_mut();
+        let border_left = borders.get_left_mut();
+        border_left.set_border_style(xf.borders.left_style().to_string());
+        handle_color(
+            border_left.get_color_mut(),
+            &global.palette,
+            xf.borders.left_icv().into(),
+        );
+        let border_top = borders.get_top_mut();
+        border_top.set_border_style(xf.borders.top_style().to_string());
+        handle_color(
+            border_top.get_color_mut(),
+            &global.palette,
+            xf.borders.top_icv().into(),
+        );
+        let border_right = borders.get_right_mut();
+        border_right.set_border_style(xf.borders.right_style().to_string());
+        handle_color(
+            border_right.get_color_mut(),
+            &global.palette,
+            xf.borders.right_icv().into(),
+        );
+        let border_bottom = borders.get_bottom_mut();
+        border_bottom.set_border_style(xf.borders.bottom_style().to_string());
+        handle_color(
+            border_bottom.get_color_mut(),
+            &global.palette,
+            xf.borders.bottom_icv().into(),
+        );
+
+        if xf.borders.diagonal_type() > 0 {
+            borders.set_diagonal_down(true);
+        }
+        if xf.borders.diagonal_type() > 1 {
+            borders.set_diagonal_up(true);
+        }
+        let border_diagonal = borders.get_diagonal_mut();
+        border_diagonal.set_border_style(xf.borders.diagonal_style().to_string());
+        handle_color(
+            border_diagonal.get_color_mut(),
+            &global.palette,
+            xf.borders.diagonal_icv().into(),
+        );
+
+        // fill
+        let fill_pattern = xstyle.get_fill_mut().get_pattern_fill_mut();
+        fill_pattern.set_pattern_type(xf.fill.pattern().into());
+        handle_color(
+            fill_pattern.get_foreground_color_mut(),
+            &global.palette,
+            xf.fill.fore_icv().into(),
+        );
+        handle_color(
+            fill_pattern.get_background_color_mut(),
+            &global.palette,
+            xf.fill.back_icv().into(),
+        );
+    }
+
+    // full colors of the XFExt record, on top of the palette colors
+    let Some(exts) = global.xf_exts.get(&(ixfe as u16)) else {
+        return;
+    };
+    for ext in exts {
+        match ext {
+            ExtProp::Foreground { color, .. } => handle_full_color(
+                xstyle
+                    .get_fill_mut()
+                    .get_pattern_fill_mut()
+                    .get_foreground_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::Background { color, .. } => handle_full_color(
+                xstyle
+                    .get_fill_mut()
+                    .get_pattern_fill_mut()
+                    .get_background_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::TopBorder { color, .. } => handle_full_color(
+                xstyle.get_borders_mut().get_top_mut().get_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::BottomBorder { color, .. } => handle_full_color(
+                xstyle.get_borders_mut().get_bottom_mut().get_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::LeftBorder { color, .. } => handle_full_color(
+                xstyle.get_borders_mut().get_left_mut().get_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::RightBorder { color, .. } => handle_full_color(
+                xstyle.get_borders_mut().get_right_mut().get_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::DiagonalBorder { color, .. } => handle_full_color(
+                xstyle.get_borders_mut().get_diagonal_mut().get_color_mut(),
+                global,
+                color,
+            ),
+            ExtProp::Text { color, .. } => {
+                handle_full_color(xstyle.get_font_mut().get_color_mut(), global, color)
+            }
+            ExtProp::Font { scheme, .. } => {
+                let scheme = match scheme {
+                    FontScheme::None => "none",
+                    FontScheme::Major => "major",
+                    FontScheme::Minor => "minor",
+                    FontScheme::Nil => continue,
+                };
+                xstyle.get_font_mut().set_scheme(scheme);
+            }
+            // the indent and path gradients are patched in by `save_xlsx`
+            ExtProp::Gradient { .. } | ExtProp::Indent { .. } => (),
+        }
+    }
+    // the gradient replaces the pattern fill
+    if let Some(gradient) = exts.iter().find_map(|ext| match ext {
+        ExtProp::Gradient { fill, .. } => Some(fill),
+        _ => None,
+    }) {
+        handle_gradient(xstyle.get_fill_mut(), global, gradient);
+    }
+}
+
+fn handle_full_color(color: &mut Color, global: &Global, full_color: &FullColorExt) {
+    let tint = full_color.tint_shade as f64 / 32767.0;
+    handle_color_value(color, global, &full_color.color_value, tint);
+}
+
+fn handle_color_value(color: &mut Color, global: &Global, value: &ColorValue, tint: f64) {
+    match value {
+        ColorValue::None { .. } => return,
+        ColorValue::Index { icv } => handle_color(color, &global.palette, *icv),
+        ColorValue::Rgba { color: rgba } => {
+            color.set_argb(format!(
+                "FF{:02X}{:02X}{:02X}",
+                rgba.red, rgba.green, rgba.blue
+            ));
+        }
+        ColorValue::Theme { theme } => {
+            color.set_theme_index(theme_index(*theme));
+        }
+    }
+    if tint != 0.0 {
+        color.set_tint(tint);
+    }
+}
+
+/// Index of a theme color in the `theme` attribute of XLSX, where the dark and light colors are swapped.
+fn theme_index(theme: ColorTheme) -> u32 {
+    match theme {
+        ColorTheme::Dark1 => 1,
+        ColorTheme::Light1 => 0,
+        ColorTheme::Dark2 => 3,
+        ColorTheme::Light2 => 2,
+        theme => theme as u32,
+    }
+}
+
+fn handle_gradient(fill: &mut Fill, global: &Global, gradient: &XFExtGradient) {
+    // umya-spreadsheet has no path gradients, they are patched in by `save_xlsx`
+    if gradient.gradient.gradient_type != GradientType::Linear {
+        return;
+    }
+    let xgradient = fill.get_gradient_fill_mut();
+    xgradient.set_degree(gradient.gradient.degree);
+    for stop in &gradient.grad_stops {
+        let mut xstop = GradientStop::default();
+        xstop.set_position(stop.position);
+        handle_color_value(xstop.get_color_mut(), global, &stop.color_value, stop.tint);
+        xgradient.set_gradient_stop(xstop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_color_value, handle_defined_name, handle_rich_text};
+    use crate::{
+        DefinedName, TextRun,
+        model::Global,
+        record::{
+            font,
+            xfext::{ColorTheme, ColorValue},
+        },
+    };
+    use binrw::BinRead;
+    use encoding_rs::UTF_16LE;
+    use std::io::Cursor;
+    use umya_spreadsheet::new_file_empty_worksheet;
+
+    fn font(name: &str) -> font::Data {
+        // height, grbit, icv, bls, sss, uls, bFamily, bCharSet, unused, fontName
+        let mut body = [200u16, 0, 0x7FFF, 400, 0].map(u16::to_le_bytes).concat();
+        body.extend([0, 0, 0, 0, name.len() as u8, 0x00]);
+        body.extend(name.as_bytes());
+        let mut bytes = (body.len() as u16).to_le_bytes().to_vec();
+        bytes.extend(body);
+        let mut data = font::Data::read_le(&mut Cursor::new(bytes)).unwrap();
+        data.decode(UTF_16LE);
+        data
+    }
+
+    #[test]
+    fn font_indexes() {
+        let global = Global {
+            fonts: ["Arial", "Bold", "Italic", "Both", "Fifth"]
+                .into_iter()
+                .map(font)
+                .collect(),
+            ..Default::default()
+        };
+        let name = |ifnt| global.font(ifnt).map(|data| data.name.as_str());
+        assert_eq!(name(0), Some("Arial"));
+        assert_eq!(name(3), Some("Both"));
+        // there is no font with index 4, the fifth FONT record has index 5
+        assert_eq!(name(4), None);
+        assert_eq!(name(5), Some("Fifth"));
+        assert_eq!(name(6), None);
+
+        // the text before the first run keeps the default font
+        let runs = [TextRun { start: 2, font: 5 }, TextRun { start: 4, font: 1 }];
+        let rich_text = handle_rich_text(&global, "ab漢字cd", &runs);
+        let elements = rich_text
+            .get_rich_text_elements()
+            .iter()
+            .map(|element| {
+                let font = element.get_font().map(|font| font.get_name().to_string());
+                (element.get_text().to_string(), font)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            elements,
+            [
+                ("ab".to_string(), None),
+                ("漢字".to_string(), Some("Fifth".to_string())),
+                ("cd".to_string(), Some("Bold".to_string())),
+            ]
+        );
+    }
+
+    fn defined_name(name: &str, sheet: Option<&str>, formula: &str) -> DefinedName {
+        DefinedName {
+            name: name.to_string(),
+            sheet: sheet.map(str::to_string),
+            hidden: false,
+            builtin: name.starts_with("_xlnm."),
+            formula: formula.to_string(),
+        }
+    }
+
+    #[test]
+    fn defined_names() {
+        let mut spreadsheet = new_file_empty_worksheet();
+        spreadsheet.new_sheet("Sheet1").unwrap();
+        spreadsheet.new_sheet("My Sheet").unwrap();
+
+        // repeated columns and rows of Print_Titles, each area is kept
+        let titles = "'My Sheet'!$A:$B,'My Sheet'!$1:$2";
+        handle_defined_name(
+            &mut spreadsheet,
+            &defined_name("_xlnm.Print_Titles", Some("My Sheet"), titles),
+        );
+        // formulas with commas or quotes that aren't a union of areas are kept whole
+        let formula = r#"Sheet1!$A:$A,INDIRECT("x")"#;
+        handle_defined_name(&mut spreadsheet, &defined_name("Mixed", None, formula));
+        handle_defined_name(&mut spreadsheet, &defined_name("Text", None, r#""a,b""#));
+        handle_defined_name(&mut spreadsheet, &defined_name("Cell", None, "Sheet1!$A$1"));
+
+        let worksheet = spreadsheet.get_sheet(&1).unwrap();
+        let xdefined_name = &worksheet.get_defined_names()[0];
+        assert_eq!(xdefined_name.get_name(), "_xlnm.Print_Titles");
+        assert_eq!(xdefined_name.get_address(), titles);
+        assert_eq!(*xdefined_name.get_local_sheet_id(), 1);
+
+        let addresses = spreadsheet
+            .get_defined_names()
+            .iter()
+            .map(|xdefined_name| xdefined_name.get_address())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            [
+                format!("({formula})"),
+                r#"("a,b")"#.to_string(),
+                "'Sheet1'!$A$1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn theme_colors() {
+        let global = Global::default();
+        let index = |theme| {
+            let mut color = umya_spreadsheet::Color::default();
+            handle_color_value(&mut color, &global, &ColorValue::Theme { theme }, 0.0);
+            *color.get_theme_index()
+        };
+        // dk1, lt1, dk2 and lt2 come in the order lt1, dk1, lt2, dk2 in XLSX
+        assert_eq!(index(ColorTheme::Dark1), 1);
+        assert_eq!(index(ColorTheme::Light1), 0);
+        assert_eq!(index(ColorTheme::Dark2), 3);
+        assert_eq!(index(ColorTheme::Light2), 2);
+        assert_eq!(index(ColorTheme::Accent1), 4);
+        assert_eq!(index(ColorTheme::FollowedHyperlink), 11);
+    }
+}
@@ -0,0 +1,1209 @@
+// the parts of the workbook umya-spreadsheet has no model for, patched into the package it writes
+use super::{
+    super::{
+        error::{Error, Result},
+        model::Global,
+        record::xfext::{ColorValue, ExtProp, GradientType, XFExtGradient},
+        workbook::{
+            AutoFilter, CellStyle, FilterCriteria, Operator, OutlineRange, Sheet, Workbook,
+        },
+    },
+    handle_style, theme_index,
+};
+use quick_xml::{
+    Reader, Writer,
+    events::{BytesStart, BytesText, Event},
+};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Seek, Write},
+};
+use umya_spreadsheet::{Spreadsheet, helper::coordinate::index_from_coordinate, writer::xlsx};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+/// Writes the spreadsheet converted from the workbook, with the parts umya-spreadsheet leaves out.
+///
+/// # Arguments
+///
+/// * `workbook` - Workbook the spreadsheet was converted from
+/// * `spreadsheet` - Spreadsheet converted from the workbook
+/// * `writer` - Destination of the XLSX content
+///
+/// # Returns
+///
+/// * `Result<()>` - Ok(()) on success, or an error on failure
+pub(super) fn write<W: Write + Seek>(
+    workbook: &Workbook,
+    mut spreadsheet: Spreadsheet,
+    writer: W,
+) -> Result<()> {
+    let global = &workbook.global;
+    // umya-spreadsheet only writes the XFs of the cells, those of the named styles are taken from
+    // a scratch worksheet, with Normal first as the default parent style
+    let mut cell_styles: Vec<&CellStyle> = workbook.styles.iter().collect();
+    cell_styles.sort_by_key(|style| style.builtin != Some(0));
+    if !cell_styles.is_empty() {
+        let worksheet = spreadsheet.new_sheet(STYLE_SHEET).map_err(Error::msg)?;
+        for (row, style) in cell_styles.iter().enumerate() {
+            let cell = worksheet.get_cell_mut((1, row as u32 + 1));
+            handle_style(cell.get_style_mut(), global, style.xf.into());
+        }
+    }
+
+    let mut content = Cursor::new(Vec::new());
+    xlsx::write_writer(&spreadsheet, &mut content)?;
+    let mut package = Package::read(content.into_inner())?;
+
+    let mut styles = Styles::new(global, package.get(STYLES).unwrap_or_default())?;
+    if !cell_styles.is_empty() {
+        // the scratch worksheet is the last one
+        let part = package.sheet_parts()?.pop().unwrap_or_default();
+        if let Some(xml) = package.get(&part) {
+            styles.add_cell_styles(cell_styles, xml)?;
+        }
+        package.remove_sheet(STYLE_SHEET, &part)?;
+    }
+    for (sheet, name) in workbook.sheets.iter().zip(package.sheet_parts()?) {
+        if let Some(xml) = package.get(&name) {
+            let xml = patch_sheet(sheet, xml, &mut styles)?;
+            package.set(&name, xml);
+        }
+    }
+    if let Some(xml) = package.get(STYLES) {
+        let xml = styles.patch(xml)?;
+        package.set(STYLES, xml);
+    }
+
+    package.write(writer)
+}
+
+const STYLES: &str = "xl/styles.xml";
+const WORKBOOK: &str = "xl/workbook.xml";
+const APP: &str = "docProps/app.xml";
+// brackets aren't allowed in sheet names, it can't be one of the workbook
+const STYLE_SHEET: &str = "[Styles]";
+
+/// Files of an XLSX package, in their original order.
+struct Package {
+    parts: Vec<(String, Vec<u8>)>,
+}
+
+impl Package {
+    fn read(content: Vec<u8>) -> Result<Self> {
+        let mut archive = ZipArchive::new(Cursor::new(content))?;
+        let mut parts = Vec::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let mut data = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut data)?;
+            parts.push((file.name().to_string(), data));
+        }
+        Ok(Package { parts })
+    }
+
+    fn get(&self, name: &str) -> Option<&[u8]> {
+        self.parts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    fn set(&mut self, name: &str, data: Vec<u8>) {
+        if let Some((_, part)) = self.parts.iter_mut().find(|(n, _)| n == name) {
+            *part = data;
+        }
+    }
+
+    fn write<W: Write + Seek>(&self, writer: W) -> Result<()> {
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(writer);
+        for (name, data) in &self.parts {
+            zip.start_file(name.as_str(), options)?;
+            zip.write_all(data)?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// Removes a worksheet from the workbook, its relationships, content types and properties.
+    fn remove_sheet(&mut self, name: &str, part: &str) -> Result<()> {
+        self.parts.retain(|(n, _)| n != part);
+
+        let mut id = None;
+        self.remove_elements(WORKBOOK, b"sheet", |e| {
+            if attribute(e, "name")?.as_deref() != Some(name) {
+                return Ok(false);
+            }
+            id = attribute(e, "r:id")?;
+            Ok(true)
+        })?;
+        self.remove_elements("xl/_rels/workbook.xml.rels", b"Relationship", |e| {
+            Ok(id.is_some() && attribute(e, "Id")? == id)
+        })?;
+        let part_name = format!("/{part}");
+        self.remove_elements("[Content_Types].xml", b"Override", |e| {
+            Ok(attribute(e, "PartName")?.as_deref() == Some(part_name.as_str()))
+        })?;
+
+        // the sheet count after the `Worksheets` heading, and the sheet name in the titles
+        let Some(xml) = self.get(APP) else {
+            return Ok(());
+        };
+        let mut titles = false;
+        let mut worksheets = false;
+        let mut count = false;
+        let mut title: Option<Vec<Event<'static>>> = None;
+        let xml = rewrite(xml, |writer, event| {
+            if let Some(events) = &mut title {
+                let end = matches!(&event, Event::End(e) if e.name().as_ref() == b"vt:lpstr");
+                events.push(event.into_owned());
+                if end {
+                    let events = title.take().unwrap_or_default();
+                    let removed = events.iter().any(|event| {
+                        matches!(event, Event::Text(t) if t.unescape().is_ok_and(|t| t == name))
+                    });
+                    if !removed {
+                        for event in events {
+                            writer.write_event(event)?;
+                        }
+                    }
+                }
+                return Ok(());
+            }
+            match event {
+                Event::Start(e) if e.name().as_ref() == b"TitlesOfParts" => {
+                    titles = true;
+                    writer.write_event(Event::Start(e))?;
+                }
+                Event::Start(e) if titles && e.name().as_ref() == b"vt:vector" => {
+                    let size = attribute(&e, "size")?.and_then(|size| size.parse::<usize>().ok());
+                    let size = size.unwrap_or_default().saturating_sub(1);
+                    writer.write_event(Event::Start(set_attributes(
+                        &e,
+                        &[("size", size.to_string())],
+                    )?))?;
+                }
+                Event::Start(e) if titles && e.name().as_ref() == b"vt:lpstr" => {
+                    title = Some(vec![Event::Start(e.into_owned())]);
+                }
+                Event::Start(e) if worksheets && e.name().as_ref() == b"vt:i4" => {
+                    count = true;
+                    writer.write_event(Event::Start(e))?;
+                }
+                Event::Text(t) if count => {
+                    let sheets = t.unescape()?.parse::<usize>().unwrap_or_default();
+                    let sheets = sheets.saturating_sub(1).to_string();
+                    writer.write_event(Event::Text(BytesText::new(&sheets)))?;
+                    (worksheets, count) = (false, false);
+                }
+                Event::Text(t) if !titles && t.unescape().is_ok_and(|t| t == "Worksheets") => {
+                    worksheets = true;
+                    writer.write_event(Event::Text(t))?;
+                }
+                event => writer.write_event(event)?,
+            }
+            Ok(())
+        })?;
+        self.set(APP, xml);
+        Ok(())
+    }
+
+    /// Removes the empty elements of a part matching `pred`.
+    fn remove_elements(
+        &mut self,
+        name: &str,
+        tag: &[u8],
+        mut pred: impl FnMut(&BytesStart) -> Result<bool>,
+    ) -> Result<()> {
+        let Some(xml) = self.get(name) else {
+            return Ok(());
+        };
+        let xml = rewrite(xml, |writer, event| {
+            match event {
+                Event::Empty(e) if e.name().as_ref() == tag && pred(&e)? => (),
+                event => writer.write_event(event)?,
+            }
+            Ok(())
+        })?;
+        self.set(name, xml);
+        Ok(())
+    }
+
+    /// Worksheet parts in the order of the sheets, from the workbook part and its relationships.
+    fn sheet_parts(&self) -> Result<Vec<String>> {
+        let mut targets = HashMap::new();
+        if let Some(xml) = self.get("xl/_rels/workbook.xml.rels") {
+            let mut reader = Reader::from_reader(xml);
+            loop {
+                match reader.read_event()? {
+                    Event::Empty(e) | Event::Start(e) if e.name().as_ref() == b"Relationship" => {
+                        if let (Some(id), Some(target)) =
+                            (attribute(&e, "Id")?, attribute(&e, "Target")?)
+                        {
+                            targets.insert(id, target);
+                        }
+                    }
+                    Event::Eof => break,
+                    _ => (),
+                }
+            }
+        }
+
+        let mut parts = Vec::new();
+        if let Some(xml) = self.get(WORKBOOK) {
+            let mut reader = Reader::from_reader(xml);
+            loop {
+                match reader.read_event()? {
+                    Event::Empty(e) | Event::Start(e) if e.name().as_ref() == b"sheet" => {
+                        let target = attribute(&e, "r:id")?.and_then(|id| targets.get(&id));
+                        if let Some(target) = target {
+                            parts.push(match target.strip_prefix('/') {
+                                Some(target) => target.to_string(),
+                                None => format!("xl/{target}"),
+                            });
+                        }
+                    }
+                    Event::Eof => break,
+                    _ => (),
+                }
+            }
+        }
+        Ok(parts)
+    }
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>> {
+    match e.try_get_attribute(name).map_err(quick_xml::Error::from)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Copies the events of an XML part, `handle` writes each of them or their replacement.
+fn rewrite(
+    xml: &[u8],
+    mut handle: impl FnMut(&mut Writer<Vec<u8>>, Event) -> Result<()>,
+) -> Result<Vec<u8>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+    loop {
+        match reader.read_event()? {
+            Event::Eof => break,
+            event => handle(&mut writer, event)?,
+        }
+    }
+    Ok(writer.into_inner())
+}
+
+fn patch_sheet(sheet: &Sheet, xml: &[u8], styles: &mut Styles) -> Result<Vec<u8>> {
+    let outline = sheet.outline();
+    // summary rows above or columns left of their details, written once in the sheet properties
+    let mut outline_pr = (!outline.summary_below || !outline.summary_right).then(|| {
+        let mut e = BytesStart::new("outlinePr");
+        if !outline.summary_below {
+            e.push_attribute(("summaryBelow", "0"));
+        }
+        if !outline.summary_right {
+            e.push_attribute(("summaryRight", "0"));
+        }
+        e
+    });
+
+    rewrite(xml, |writer, event| {
+        match event {
+            // umya-spreadsheet writes no outline properties, the sheet properties come first
+            Event::Empty(e) if e.name().as_ref() == b"sheetPr" => match outline_pr.take() {
+                Some(pr) => {
+                    writer.write_event(Event::Start(e.borrow()))?;
+                    writer.write_event(Event::Empty(pr))?;
+                    writer.write_event(Event::End(e.to_end()))?;
+                }
+                None => writer.write_event(Event::Empty(e))?,
+            },
+            // after the tab color
+            Event::End(e) if e.name().as_ref() == b"sheetPr" => {
+                if let Some(pr) = outline_pr.take() {
+                    writer.write_event(Event::Empty(pr))?;
+                }
+                writer.write_event(Event::End(e))?;
+            }
+            Event::Empty(e) if e.name().as_ref() == b"dimension" => {
+                if let Some(pr) = outline_pr.take() {
+                    writer
+                        .create_element("sheetPr")
+                        .write_inner_content(|writer| writer.write_event(Event::Empty(pr)))?;
+                }
+                writer.write_event(Event::Empty(e))?;
+            }
+            // umya-spreadsheet has no outline levels nor collapsed state on its rows and columns
+            Event::Start(e) if e.name().as_ref() == b"row" => {
+                writer.write_event(Event::Start(outline_row(e, &outline.rows)?))?;
+            }
+            Event::Empty(e) if e.name().as_ref() == b"row" => {
+                writer.write_event(Event::Empty(outline_row(e, &outline.rows)?))?;
+            }
+            Event::Empty(e) if e.name().as_ref() == b"col" => {
+                write_outline_cols(writer, e, &outline.cols)?;
+            }
+            // formatting of the cell XF umya-spreadsheet has no model for
+            Event::Start(e) if e.name().as_ref() == b"c" => {
+                writer.write_event(Event::Start(cell_xf(e, sheet, styles)?))?;
+            }
+            Event::Empty(e) if e.name().as_ref() == b"c" => {
+                writer.write_event(Event::Empty(cell_xf(e, sheet, styles)?))?;
+            }
+            // umya-spreadsheet only writes the range of the filter
+            Event::Empty(e) if e.name().as_ref() == b"autoFilter" => match sheet.auto_filter() {
+                Some(auto_filter) if !auto_filter.columns.is_empty() => {
+                    writer.write_event(Event::Start(e.borrow()))?;
+                    write_filter_columns(writer, auto_filter)?;
+                    writer.write_event(Event::End(e.to_end()))?;
+                }
+                _ => writer.write_event(Event::Empty(e))?,
+            },
+            event => writer.write_event(event)?,
+        }
+        Ok(())
+    })
+}
+
+fn cell_xf<'a>(e: BytesStart<'a>, sheet: &Sheet, styles: &mut Styles) -> Result<BytesStart<'a>> {
+    let Some(coordinate) = attribute(&e, "r")? else {
+        return Ok(e);
+    };
+    let (Some(col), Some(row), ..) = index_from_coordinate(coordinate) else {
+        return Ok(e);
+    };
+    let Some(cell) = sheet.cell((row - 1) as u16, (col - 1) as u16) else {
+        return Ok(e);
+    };
+    let s = attribute(&e, "s")?
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let xf = styles.xf(s, cell.xf)?;
+    if xf == s {
+        return Ok(e);
+    }
+    set_attributes(&e, &[("s", xf.to_string())])
+}
+
+/// Copy of an element without one of its attributes.
+fn without_attribute(e: &BytesStart, key: &str) -> Result<BytesStart<'static>> {
+    let mut element = BytesStart::new(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+    for attr in e.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        if attr.key.as_ref() != key.as_bytes() {
+            element.push_attribute(attr);
+        }
+    }
+    Ok(element)
+}
+
+/// Copy of an element with some of its attributes replaced or added.
+fn set_attributes(e: &BytesStart, values: &[(&str, String)]) -> Result<BytesStart<'static>> {
+    let mut element = BytesStart::new(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+    for attr in e.attributes() {
+        let attr = attr.map_err(quick_xml::Error::from)?;
+        if !values
+            .iter()
+            .any(|(key, _)| key.as_bytes() == attr.key.as_ref())
+        {
+            element.push_attribute(attr);
+        }
+    }
+    for (key, value) in values {
+        element.push_attribute((*key, value.as_str()));
+    }
+    Ok(element)
+}
+
+/// Outline range of a zero-based row or column.
+fn outline_range(ranges: &[OutlineRange], index: u32) -> Option<&OutlineRange> {
+    ranges
+        .iter()
+        .find(|range| (range.first as u32..=range.last as u32).contains(&index))
+}
+
+fn push_outline(e: &mut BytesStart, range: Option<&OutlineRange>) {
+    let Some(range) = range else {
+        return;
+    };
+    if range.level > 0 {
+        e.push_attribute(("outlineLevel", range.level.to_string().as_str()));
+    }
+    if range.collapsed {
+        e.push_attribute(("collapsed", "1"));
+    }
+}
+
+fn outline_row<'a>(mut e: BytesStart<'a>, ranges: &[OutlineRange]) -> Result<BytesStart<'a>> {
+    let row = attribute(&e, "r")?.and_then(|r| r.parse::<u32>().ok());
+    let range = row.and_then(|row| outline_range(ranges, row.checked_sub(1)?));
+    push_outline(&mut e, range);
+    Ok(e)
+}
+
+/// Writes a `<col>` element for each outline range it spans, umya-spreadsheet merges adjacent columns.
+fn write_outline_cols(
+    writer: &mut Writer<Vec<u8>>,
+    e: BytesStart,
+    ranges: &[OutlineRange],
+) -> Result<()> {
+    let number = |name| -> Result<Option<u32>> {
+        Ok(attribute(&e, name)?.and_then(|n| n.parse::<u32>().ok()))
+    };
+    let (Some(min), Some(max)) = (number("min")?, number("max")?) else {
+        writer.write_event(Event::Empty(e))?;
+        return Ok(());
+    };
+
+    let mut first = min;
+    while first <= max {
+        let range = outline_range(ranges, first - 1);
+        let mut last = first;
+        while last < max && outline_range(ranges, last) == range {
+            last += 1;
+        }
+
+        let mut col = BytesStart::new("col");
+        col.push_attribute(("min", first.to_string().as_str()));
+        col.push_attribute(("max", last.to_string().as_str()));
+        for attr in e.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            if !matches!(attr.key.as_ref(), b"min" | b"max") {
+                col.push_attribute(attr);
+            }
+        }
+        push_outline(&mut col, range);
+        writer.write_event(Event::Empty(col))?;
+        first = last + 1;
+    }
+    Ok(())
+}
+
+fn write_filter_columns(writer: &mut Writer<Vec<u8>>, auto_filter: &AutoFilter) -> Result<()> {
+    for column in &auto_filter.columns {
+        let col_id = column.col.to_string();
+        let element = writer
+            .create_element("filterColumn")
+            .with_attribute(("colId", col_id.as_str()));
+        element.write_inner_content(|writer| {
+            match &column.criteria {
+                FilterCriteria::Values(values) => {
+                    let mut filters = writer.create_element("filters");
+                    // blanks are selected with an attribute, not a value
+                    if values.iter().any(String::is_empty) {
+                        filters = filters.with_attribute(("blank", "1"));
+                    }
+                    if values.iter().all(String::is_empty) {
+                        filters.write_empty()?;
+                        return Ok(());
+                    }
+                    filters.write_inner_content(|writer| {
+                        for value in values.iter().filter(|v| !v.is_empty()) {
+                            writer
+                                .create_element("filter")
+                                .with_attribute(("val", value.as_str()))
+                                .write_empty()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                FilterCriteria::Custom { and, conditions } => {
+                    let mut filters = writer.create_element("customFilters");
+                    if *and {
+                        filters = filters.with_attribute(("and", "1"));
+                    }
+                    filters.write_inner_content(|writer| {
+                        for condition in conditions {
+                            let mut filter = writer.create_element("customFilter");
+                            if let Some(operator) = filter_operator(condition.operator) {
+                                filter = filter.with_attribute(("operator", operator));
+                            }
+                            filter
+                                .with_attribute(("val", condition.value.as_str()))
+                                .write_empty()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                FilterCriteria::Top10 {
+                    top,
+                    percent,
+                    value,
+                } => {
+                    let mut filter = writer.create_element("top10");
+                    if !top {
+                        filter = filter.with_attribute(("top", "0"));
+                    }
+                    if *percent {
+                        filter = filter.with_attribute(("percent", "1"));
+                    }
+                    filter
+                        .with_attribute(("val", value.to_string().as_str()))
+                        .write_empty()?;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// Formatting of a cell XF umya-spreadsheet has no model for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct XfExtra {
+    indent: u16,
+    /// `<fill>` element of a path gradient
+    fill: Option<String>,
+    /// Cell style XF of the parent style, umya-spreadsheet writes 0 for all
+    xf_id: usize,
+}
+
+impl XfExtra {
+    fn new(global: &Global, ixfe: u16) -> Result<Self> {
+        let mut extra = XfExtra::default();
+        if let Some(xf) = global.xfs.get(ixfe as usize) {
+            extra.indent = xf.alignment.indent().into();
+        }
+        for ext in global.xf_exts.get(&ixfe).into_iter().flatten() {
+            match ext {
+                // up to 250 levels instead of 15
+                ExtProp::Indent { level, .. } => extra.indent = *level,
+                ExtProp::Gradient { fill, .. }
+                    if fill.gradient.gradient_type == GradientType::Rectangular =>
+                {
+                    extra.fill = Some(path_gradient(global, fill)?);
+                }
+                _ => (),
+            }
+        }
+        Ok(extra)
+    }
+}
+
+fn path_gradient(global: &Global, gradient: &XFExtGradient) -> Result<String> {
+    let mut writer = Writer::new(Vec::new());
+    let props = &gradient.gradient;
+    writer
+        .create_element("fill")
+        .write_inner_content(|writer| {
+            writer
+                .create_element("gradientFill")
+                .with_attribute(("type", "path"))
+                .with_attribute(("left", props.fill2left.to_string().as_str()))
+                .with_attribute(("right", props.fill2right.to_string().as_str()))
+                .with_attribute(("top", props.fill2top.to_string().as_str()))
+                .with_attribute(("bottom", props.fill2bottom.to_string().as_str()))
+                .write_inner_content(|writer| {
+                    for stop in &gradient.grad_stops {
+                        let mut color = BytesStart::new("color");
+                        match &stop.color_value {
+                            ColorValue::None { .. } => color.push_attribute(("auto", "1")),
+                            ColorValue::Index { icv } => {
+                                match global.palette.as_ref().and_then(|p| p.get(*icv as usize)) {
+                                    Some(argb) => color.push_attribute(("rgb", argb.as_str())),
+                                    None => {
+                                        color.push_attribute(("indexed", icv.to_string().as_str()))
+                                    }
+                                }
+                            }
+                            ColorValue::Rgba { color: rgba } => {
+                                let argb = format!(
+                                    "FF{:02X}{:02X}{:02X}",
+                                    rgba.red, rgba.green, rgba.blue
+                                );
+                                color.push_attribute(("rgb", argb.as_str()));
+                            }
+                            ColorValue::Theme { theme } => color.push_attribute((
+                                "theme",
+                                theme_index(*theme).to_string().as_str(),
+                            )),
+                        }
+                        if stop.tint != 0.0 {
+                            color.push_attribute(("tint", stop.tint.to_string().as_str()));
+                        }
+                        writer
+                            .create_element("stop")
+                            .with_attribute(("position", stop.position.to_string().as_str()))
+                            .write_inner_content(|writer| {
+                                writer.write_event(Event::Empty(color))
+                            })?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+    Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+}
+
+/// Cell XFs added to those of umya-spreadsheet, for the cells with extra formatting, and the named styles.
+struct Styles<'a> {
+    global: &'a Global,
+    extras: HashMap<u16, XfExtra>,
+    /// Events of each cell XF written by umya-spreadsheet
+    xfs: Vec<Vec<Event<'static>>>,
+    /// umya-spreadsheet XF and extra formatting of each added XF
+    variants: Vec<(usize, XfExtra)>,
+    indexes: HashMap<(usize, XfExtra), usize>,
+    /// Named styles, with the umya-spreadsheet XF and extra formatting of their style XF
+    cell_styles: Vec<(&'a CellStyle, usize, XfExtra)>,
+    /// Cell style XF of each style XF
+    style_ids: HashMap<u16, usize>,
+}
+
+impl<'a> Styles<'a> {
+    fn new(global: &'a Global, xml: &[u8]) -> Result<Self> {
+        let mut xfs = Vec::new();
+        let mut reader = Reader::from_reader(xml);
+        let mut in_cell_xfs = false;
+        let mut xf: Option<Vec<Event<'static>>> = None;
+        loop {
+            let event = reader.read_event()?;
+            if let Some(events) = &mut xf {
+                let end = matches!(&event, Event::End(e) if e.name().as_ref() == b"xf");
+                events.push(event.into_owned());
+                if end {
+                    xfs.extend(xf.take());
+                }
+                continue;
+            }
+            match event {
+                Event::Start(e) if e.name().as_ref() == b"cellXfs" => in_cell_xfs = true,
+                Event::End(e) if e.name().as_ref() == b"cellXfs" => break,
+                Event::Start(e) if in_cell_xfs && e.name().as_ref() == b"xf" => {
+                    xf = Some(vec![Event::Start(e.into_owned())]);
+                }
+                Event::Empty(e) if in_cell_xfs && e.name().as_ref() == b"xf" => {
+                    xfs.push(vec![Event::Empty(e.into_owned())]);
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(Styles {
+            global,
+            extras: HashMap::new(),
+            xfs,
+            variants: Vec::new(),
+            indexes: HashMap::new(),
+            cell_styles: Vec::new(),
+            style_ids: HashMap::new(),
+        })
+    }
+
+    /// Takes the XFs of the named styles from the scratch worksheet, one style per row.
+    fn add_cell_styles(&mut self, styles: Vec<&'a CellStyle>, xml: &[u8]) -> Result<()> {
+        let mut xfs = HashMap::new();
+        let mut reader = Reader::from_reader(xml);
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) | Event::Empty(e) if e.name().as_ref() == b"c" => {
+                    let row = attribute(&e, "r")?.and_then(|r| index_from_coordinate(r).1);
+                    let s = attribute(&e, "s")?.and_then(|s| s.parse().ok());
+                    if let Some(row) = row {
+                        xfs.insert(row as usize - 1, s.unwrap_or(0));
+                    }
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+
+        for (index, style) in styles.into_iter().enumerate() {
+            let extra = XfExtra::new(self.global, style.xf)?;
+            let s = xfs.get(&index).copied().unwrap_or(0);
+            self.cell_styles.push((style, s, extra));
+            self.style_ids.insert(style.xf, index);
+        }
+        Ok(())
+    }
+
+    /// Cell XF of a cell, the one of umya-spreadsheet or its variant with the extra formatting.
+    fn xf(&mut self, s: usize, ixfe: u16) -> Result<usize> {
+        let base = self.xfs.len();
+        if s >= base {
+            return Ok(s);
+        }
+        if !self.extras.contains_key(&ixfe) {
+            let mut extra = XfExtra::new(self.global, ixfe)?;
+            extra.xf_id = self
+                .global
+                .style_xf(ixfe)
+                .and_then(|xf| self.style_ids.get(&xf))
+                .copied()
+                .unwrap_or(0);
+            self.extras.insert(ixfe, extra);
+        }
+        let extra = &self.extras[&ixfe];
+        if *extra == XfExtra::default() {
+            return Ok(s);
+        }
+        let key = (s, extra.clone());
+        if let Some(&index) = self.indexes.get(&key) {
+            return Ok(index);
+        }
+        let index = base + self.variants.len();
+        self.variants.push(key.clone());
+        self.indexes.insert(key, index);
+        Ok(index)
+    }
+
+    fn patch(&self, xml: &[u8]) -> Result<Vec<u8>> {
+        if self.variants.is_empty() && self.cell_styles.is_empty() {
+            return Ok(xml.to_vec());
+        }
+        let mut fills: Vec<&str> = Vec::new();
+        let extras = self.variants.iter().map(|(_, extra)| extra);
+        for extra in extras.chain(self.cell_styles.iter().map(|(_, _, extra)| extra)) {
+            if let Some(fill) = &extra.fill
+                && !fills.contains(&fill.as_str())
+            {
+                fills.push(fill);
+            }
+        }
+
+        let mut fill_base = 0;
+        let fill_id = |fill_base, extra: &XfExtra| {
+            let fill = extra.fill.as_ref()?;
+            Some(fill_base + fills.iter().position(|f| f == fill)?)
+        };
+        rewrite(xml, |writer, event| {
+            match event {
+                Event::Start(e) if e.name().as_ref() == b"fills" => {
+                    fill_base = attribute(&e, "count")?
+                        .and_then(|count| count.parse().ok())
+                        .unwrap_or(0);
+                    let count = fill_base + fills.len();
+                    writer.write_event(Event::Start(set_attributes(
+                        &e,
+                        &[("count", count.to_string())],
+                    )?))?;
+                }
+                Event::End(e) if e.name().as_ref() == b"fills" => {
+                    for fill in &fills {
+                        writer.get_mut().extend_from_slice(fill.as_bytes());
+                    }
+                    writer.write_event(Event::End(e))?;
+                }
+                // the XFs of the named styles come before the cell XFs
+                Event::Start(e) if e.name().as_ref() == b"cellXfs" => {
+                    if !self.cell_styles.is_empty() {
+                        let count = self.cell_styles.len().to_string();
+                        let mut xfs = BytesStart::new("cellStyleXfs");
+                        xfs.push_attribute(("count", count.as_str()));
+                        writer.write_event(Event::Start(xfs.borrow()))?;
+                        for (_, s, extra) in &self.cell_styles {
+                            let Some(events) = self.xfs.get(*s) else {
+                                continue;
+                            };
+                            // style XFs have no parent
+                            let mut events = events.clone();
+                            if let Some(Event::Start(e) | Event::Empty(e)) = events.first_mut() {
+                                *e = without_attribute(e, "xfId")?;
+                            }
+                            write_xf_variant(writer, &events, extra, fill_id(fill_base, extra))?;
+                        }
+                        writer.write_event(Event::End(xfs.to_end()))?;
+                    }
+                    let count = self.xfs.len() + self.variants.len();
+                    writer.write_event(Event::Start(set_attributes(
+                        &e,
+                        &[("count", count.to_string())],
+                    )?))?;
+                }
+                Event::End(e) if e.name().as_ref() == b"cellXfs" => {
+                    for (s, extra) in &self.variants {
+                        if let Some(events) = self.xfs.get(*s) {
+                            write_xf_variant(writer, events, extra, fill_id(fill_base, extra))?;
+                        }
+                    }
+                    writer.write_event(Event::End(e))?;
+                    if !self.cell_styles.is_empty() {
+                        write_cell_styles(writer, &self.cell_styles)?;
+                    }
+                }
+                event => writer.write_event(event)?,
+            }
+            Ok(())
+        })
+    }
+}
+
+fn write_cell_styles(
+    writer: &mut Writer<Vec<u8>>,
+    cell_styles: &[(&CellStyle, usize, XfExtra)],
+) -> Result<()> {
+    let count = cell_styles.len().to_string();
+    writer
+        .create_element("cellStyles")
+        .with_attribute(("count", count.as_str()))
+        .write_inner_content(|writer| {
+            for (xf_id, (style, ..)) in cell_styles.iter().enumerate() {
+                let mut e = BytesStart::new("cellStyle");
+                e.push_attribute(("name", style.name.as_str()));
+                e.push_attribute(("xfId", xf_id.to_string().as_str()));
+                if let Some(builtin) = style.builtin {
+                    e.push_attribute(("builtinId", builtin.to_string().as_str()));
+                }
+                if let Some(level) = style.outline_level {
+                    e.push_attribute(("iLevel", level.to_string().as_str()));
+                }
+                if style.hidden {
+                    e.push_attribute(("hidden", "1"));
+                }
+                writer.write_event(Event::Empty(e))?;
+            }
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/// Writes a copy of a cell XF, with the indent, fill and parent style of the extra formatting.
+fn write_xf_variant(
+    writer: &mut Writer<Vec<u8>>,
+    events: &[Event],
+    extra: &XfExtra,
+    fill_id: Option<usize>,
+) -> Result<()> {
+    let mut values = Vec::new();
+    if let Some(fill_id) = fill_id {
+        values.push(("fillId", fill_id.to_string()));
+        values.push(("applyFill", "1".to_string()));
+    }
+    if extra.indent > 0 {
+        values.push(("applyAlignment", "1".to_string()));
+    }
+    if extra.xf_id > 0 {
+        values.push(("xfId", extra.xf_id.to_string()));
+    }
+    let indent = [("indent", extra.indent.to_string())];
+    let has_alignment = events
+        .iter()
+        .any(|event| matches!(event, Event::Empty(e) if e.name().as_ref() == b"alignment"));
+
+    for event in events {
+        match event {
+            Event::Start(e) if e.name().as_ref() == b"xf" => {
+                writer.write_event(Event::Start(set_attributes(e, &values)?))?;
+                // the alignment comes first
+                if extra.indent > 0 && !has_alignment {
+                    writer.write_event(Event::Empty(set_attributes(
+                        &BytesStart::new("alignment"),
+                        &indent,
+                    )?))?;
+                }
+            }
+            Event::Empty(e) if e.name().as_ref() == b"xf" => {
+                let xf = set_attributes(e, &values)?;
+                if extra.indent > 0 {
+                    writer.write_event(Event::Start(xf.borrow()))?;
+                    writer.write_event(Event::Empty(set_attributes(
+                        &BytesStart::new("alignment"),
+                        &indent,
+                    )?))?;
+                    writer.write_event(Event::End(xf.to_end()))?;
+                } else {
+                    writer.write_event(Event::Empty(xf))?;
+                }
+            }
+            Event::Empty(e) if e.name().as_ref() == b"alignment" && extra.indent > 0 => {
+                writer.write_event(Event::Empty(set_attributes(e, &indent)?))?;
+            }
+            event => writer.write_event(event.borrow())?,
+        }
+    }
+    Ok(())
+}
+
+/// Operator of a custom filter, None for `equal`, the default.
+fn filter_operator(operator: Operator) -> Option<&'static str> {
+    match operator {
+        Operator::NotEqual => Some("notEqual"),
+        Operator::GreaterThan => Some("greaterThan"),
+        Operator::LessThan => Some("lessThan"),
+        Operator::GreaterThanOrEqual => Some("greaterThanOrEqual"),
+        Operator::LessThanOrEqual => Some("lessThanOrEqual"),
+        // ranges are never read from the filter records
+        Operator::Equal | Operator::Between | Operator::NotBetween => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        record::xf,
+        workbook::{Cell, CellRange, CellValue, FilterColumn, FilterCondition, SheetState},
+    };
+    use binrw::BinRead;
+
+    fn patch(sheet: &Sheet, xml: &str) -> String {
+        let global = Global::default();
+        let mut styles = Styles::new(&global, b"").unwrap();
+        String::from_utf8(patch_sheet(sheet, xml.as_bytes(), &mut styles).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn filter_columns() {
+        let mut sheet = Sheet::new("Sheet1".to_string(), SheetState::Visible);
+        let xml = r#"<worksheet><sheetData/><autoFilter ref="A1:D10"/></worksheet>"#;
+        assert_eq!(patch(&sheet, xml), xml);
+
+        let condition = |operator, value: &str| FilterCondition {
+            operator,
+            value: value.to_string(),
+        };
+        sheet.auto_filter = Some(AutoFilter {
+            range: CellRange {
+                row_first: 0,
+                row_last: 9,
+                col_first: 0,
+                col_last: 3,
+            },
+            columns: vec![
+                FilterColumn {
+                    col: 0,
+                    criteria: FilterCriteria::Values(vec!["Apple".into(), "".into()]),
+                },
+                FilterColumn {
+                    col: 1,
+                    criteria: FilterCriteria::Custom {
+                        and: true,
+                        conditions: vec![
+                            condition(Operator::GreaterThanOrEqual, "2.5"),
+                            condition(Operator::Equal, "A&B"),
+                        ],
+                    },
+                },
+                FilterColumn {
+                    col: 2,
+                    criteria: FilterCriteria::Top10 {
+                        top: false,
+                        percent: true,
+                        value: 10,
+                    },
+                },
+            ],
+            filtered: true,
+        });
+        assert_eq!(
+            patch(&sheet, xml),
+            concat!(
+                r#"<worksheet><sheetData/><autoFilter ref="A1:D10">"#,
+                r#"<filterColumn colId="0"><filters blank="1"><filter val="Apple"/></filters></filterColumn>"#,
+                r#"<filterColumn colId="1"><customFilters and="1">"#,
+                r#"<customFilter operator="greaterThanOrEqual" val="2.5"/><customFilter val="A&amp;B"/>"#,
+                r#"</customFilters></filterColumn>"#,
+                r#"<filterColumn colId="2"><top10 top="0" percent="1" val="10"/></filterColumn>"#,
+                r#"</autoFilter></worksheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn outlines() {
+        let mut sheet = Sheet::new("Sheet1".to_string(), SheetState::Visible);
+        let range = |first, last, level, collapsed| OutlineRange {
+            first,
+            last,
+            level,
+            collapsed,
+        };
+        sheet.outline.summary_below = false;
+        sheet.outline.rows = vec![range(1, 1, 1, false), range(2, 2, 0, true)];
+        sheet.outline.cols = vec![range(1, 2, 1, false), range(3, 3, 0, true)];
+        let xml = concat!(
+            r#"<worksheet><dimension ref="A1"/><cols><col min="1" max="5" width="9"/></cols>"#,
+            r#"<sheetData><row r="1"/><row r="2" hidden="1"><c r="A2"/></row><row r="3"/></sheetData></worksheet>"#,
+        );
+        assert_eq!(
+            patch(&sheet, xml),
+            concat!(
+                r#"<worksheet><sheetPr><outlinePr summaryBelow="0"/></sheetPr><dimension ref="A1"/><cols>"#,
+                r#"<col min="1" max="1" width="9"/><col min="2" max="3" width="9" outlineLevel="1"/>"#,
+                r#"<col min="4" max="4" width="9" collapsed="1"/><col min="5" max="5" width="9"/></cols>"#,
+                r#"<sheetData><row r="1"/><row r="2" hidden="1" outlineLevel="1"><c r="A2"/></row>"#,
+                r#"<row r="3" collapsed="1"/></sheetData></worksheet>"#,
+            )
+        );
+
+        // after the tab color of the existing properties
+        let xml = r#"<worksheet><sheetPr><tabColor rgb="FFFF0000"/></sheetPr><dimension ref="A1"/></worksheet>"#;
+        assert_eq!(
+            patch(&sheet, xml),
+            r#"<worksheet><sheetPr><tabColor rgb="FFFF0000"/><outlinePr summaryBelow="0"/></sheetPr><dimension ref="A1"/></worksheet>"#
+        );
+    }
+
+    #[test]
+    fn xf_variants() {
+        // cell XFs of bottom aligned cells
+        let xf = |indent| {
+            let mut bytes = vec![20, 0, 0, 0, 0, 0, 0x01, 0, 0x20, 0, indent, 0];
+            bytes.extend([0; 10]);
+            xf::Data::read_le(&mut Cursor::new(&bytes)).unwrap()
+        };
+        // path gradient from red to the first accent color
+        let mut bytes = vec![0x06, 0, 0, 0];
+        bytes.extend(1u32.to_le_bytes());
+        for value in [0.0f64, 0.5, 0.5, 0.5, 0.5] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend([0x02, 0, 0xFF, 0, 0, 0]);
+        bytes.extend([0.0f64, 0.0].iter().flat_map(|v| v.to_le_bytes()));
+        bytes.extend([0x03, 0, 0x04, 0, 0, 0]);
+        bytes.extend([1.0f64, 0.5].iter().flat_map(|v| v.to_le_bytes()));
+        let gradient = ExtProp::read_le(&mut Cursor::new(&bytes)).unwrap();
+
+        let global = Global {
+            xfs: vec![xf(2), xf(0)],
+            xf_exts: HashMap::from([(1, vec![gradient])]),
+            ..Default::default()
+        };
+        let mut sheet = Sheet::new("Sheet1".to_string(), SheetState::Visible);
+        for (col, xf) in [(0, 0), (1, 1), (2, 1)] {
+            let value = CellValue::Number(1.0);
+            let text_format = None;
+            let row = 0;
+            sheet.cells.insert(
+                (row, col),
+                Cell {
+                    row,
+                    col,
+                    value,
+                    xf,
+                    text_format,
+                },
+            );
+        }
+
+        let styles_xml = concat!(
+            r#"<styleSheet><fills count="1"><fill/></fills><cellXfs count="2">"#,
+            r#"<xf fontId="0"/><xf fontId="1"><alignment horizontal="left"/></xf></cellXfs></styleSheet>"#,
+        );
+        let mut styles = Styles::new(&global, styles_xml.as_bytes()).unwrap();
+        let xml = r#"<row r="1"><c r="A1" s="1"/><c r="B1"/><c r="C1" s="0"><v>1</v></c></row>"#;
+        let xml = patch_sheet(&sheet, xml.as_bytes(), &mut styles).unwrap();
+        assert_eq!(
+            String::from_utf8(xml).unwrap(),
+            r#"<row r="1"><c r="A1" s="2"/><c r="B1" s="3"/><c r="C1" s="3"><v>1</v></c></row>"#
+        );
+        let xml = styles.patch(styles_xml.as_bytes()).unwrap();
+        assert_eq!(
+            String::from_utf8(xml).unwrap(),
+            concat!(
+                r#"<styleSheet><fills count="2"><fill/><fill><gradientFill type="path" left="0.5" right="0.5" top="0.5" bottom="0.5">"#,
+                r#"<stop position="0"><color rgb="FFFF0000"/></stop><stop position="1"><color theme="4" tint="0.5"/></stop>"#,
+                r#"</gradientFill></fill></fills><cellXfs count="4"><xf fontId="0"/><xf fontId="1"><alignment horizontal="left"/></xf>"#,
+                r#"<xf fontId="1" applyAlignment="1"><alignment horizontal="left" indent="2"/></xf>"#,
+                r#"<xf fontId="0" fillId="1" applyFill="1"/></cellXfs></styleSheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn cell_styles() {
+        // style XFs, then a cell XF of the second style
+        let xf = |protection: u16| {
+            let mut bytes = vec![20, 0, 0, 0, 0, 0];
+            bytes.extend(protection.to_le_bytes());
+            bytes.extend([0x20, 0, 0, 0]);
+            bytes.extend([0; 10]);
+            xf::Data::read_le(&mut Cursor::new(&bytes)).unwrap()
+        };
+        let global = Global {
+            xfs: vec![xf(0xFFF5), xf(0xFFF5), xf(0x0011)],
+            ..Default::default()
+        };
+        let style = |name: &str, xf, builtin| CellStyle {
+            name: name.to_string(),
+            xf,
+            builtin,
+            outline_level: None,
+            hidden: false,
+            category: None,
+        };
+        let normal = style("Normal", 0, Some(0));
+        let custom = style("My Style", 1, None);
+
+        let styles_xml = concat!(
+            r#"<styleSheet><borders count="1"><border/></borders><cellXfs count="2">"#,
+            r#"<xf fontId="0" xfId="0"/><xf fontId="1" xfId="0"/></cellXfs><dxfs count="0"/></styleSheet>"#,
+        );
+        let mut styles = Styles::new(&global, styles_xml.as_bytes()).unwrap();
+        let scratch = r#"<row r="1"><c r="A1" s="0"/></row><row r="2"><c r="A2" s="1"/></row>"#;
+        styles
+            .add_cell_styles(vec![&normal, &custom], scratch.as_bytes())
+            .unwrap();
+        assert_eq!(styles.xf(0, 0).unwrap(), 0);
+        assert_eq!(styles.xf(0, 2).unwrap(), 2);
+
+        let xml = styles.patch(styles_xml.as_bytes()).unwrap();
+        assert_eq!(
+            String::from_utf8(xml).unwrap(),
+            concat!(
+                r#"<styleSheet><borders count="1"><border/></borders>"#,
+                r#"<cellStyleXfs count="2"><xf fontId="0"/><xf fontId="1"/></cellStyleXfs>"#,
+                r#"<cellXfs count="3"><xf fontId="0" xfId="0"/><xf fontId="1" xfId="0"/><xf fontId="0" xfId="1"/></cellXfs>"#,
+                r#"<cellStyles count="2"><cellStyle name="Normal" xfId="0" builtinId="0"/>"#,
+                r#"<cellStyle name="My Style" xfId="1"/></cellStyles><dxfs count="0"/></styleSheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn remove_sheet() {
+        let part = |name: &str, xml: &str| (name.to_string(), xml.as_bytes().to_vec());
+        let mut package = Package {
+            parts: vec![
+                part(
+                    "[Content_Types].xml",
+                    r#"<Types><Override PartName="/xl/worksheets/sheet1.xml"/><Override PartName="/xl/worksheets/sheet2.xml"/></Types>"#,
+                ),
+                part(
+                    APP,
+                    concat!(
+                        r#"<Properties><HeadingPairs><vt:vector size="2"><vt:variant><vt:lpstr>Worksheets</vt:lpstr></vt:variant>"#,
+                        r#"<vt:variant><vt:i4>2</vt:i4></vt:variant></vt:vector></HeadingPairs><TitlesOfParts><vt:vector size="2">"#,
+                        r#"<vt:lpstr>Sheet1</vt:lpstr><vt:lpstr>[Styles]</vt:lpstr></vt:vector></TitlesOfParts></Properties>"#,
+                    ),
+                ),
+                part(
+                    WORKBOOK,
+                    r#"<workbook><sheets><sheet name="Sheet1" r:id="rId1"/><sheet name="[Styles]" r:id="rId2"/></sheets></workbook>"#,
+                ),
+                part(
+                    "xl/_rels/workbook.xml.rels",
+                    r#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Target="worksheets/sheet2.xml"/></Relationships>"#,
+                ),
+                part("xl/worksheets/sheet1.xml", "<worksheet/>"),
+                part("xl/worksheets/sheet2.xml", "<worksheet/>"),
+            ],
+        };
+        assert_eq!(
+            package.sheet_parts().unwrap(),
+            ["xl/worksheets/sheet1.xml", "xl/worksheets/sheet2.xml"]
+        );
+
+        package
+            .remove_sheet(STYLE_SHEET, "xl/worksheets/sheet2.xml")
+            .unwrap();
+        let get = |name| String::from_utf8(package.get(name).unwrap().to_vec()).unwrap();
+        assert_eq!(package.sheet_parts().unwrap(), ["xl/worksheets/sheet1.xml"]);
+        assert!(package.get("xl/worksheets/sheet2.xml").is_none());
+        assert_eq!(
+            get("[Content_Types].xml"),
+            r#"<Types><Override PartName="/xl/worksheets/sheet1.xml"/></Types>"#
+        );
+        assert_eq!(
+            get(APP),
+            concat!(
+                r#"<Properties><HeadingPairs><vt:vector size="2"><vt:variant><vt:lpstr>Worksheets</vt:lpstr></vt:variant>"#,
+                r#"<vt:variant><vt:i4>1</vt:i4></vt:variant></vt:vector></HeadingPairs><TitlesOfParts><vt:vector size="1">"#,
+                r#"<vt:lpstr>Sheet1</vt:lpstr></vt:vector></TitlesOfParts></Properties>"#,
+            )
+        );
+    }
+}